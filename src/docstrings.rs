@@ -7,6 +7,8 @@ pub struct Argument {
     pub name: String,
     pub type_: Option<String>,
     pub default: Option<String>,
+    /// Marked as `optional` next to its type
+    pub optional: bool,
     pub description: Option<String>,
 }
 
@@ -27,10 +29,84 @@ pub struct Docstring {
     pub title: String,
     pub description: String,
     pub returns: String,
+    /// The type of the return value when it's documented, like `:rtype:`
+    pub returns_type: Option<String>,
+    pub yields: String,
+    pub yields_type: Option<String>,
+    /// The values sent to a generator with `send()`
+    pub receives: String,
+    pub receives_type: Option<String>,
     pub body: Vec<BodyPart>,
     pub arguments: Vec<Argument>,
     pub private_arguments: Vec<Argument>,
     pub raises: Vec<Raises>,
+    pub see_also: String,
+    pub notes: String,
+}
+
+// splits a documented type from the `optional` and `default` markers,
+// for example `int, optional` or `str, default: "a"`
+fn parse_type(type_: &str) -> (Option<String>, Option<String>, bool) {
+    let mut parts = Vec::new();
+    let mut default = None;
+    let mut optional = false;
+
+    for part in type_.split(", ") {
+        let part = part.trim();
+
+        if part == "optional" {
+            optional = true;
+        } else if let Some(value) = part
+            .strip_prefix("default")
+            .filter(|rest| rest.starts_with(|c: char| c == ':' || c == '=' || c == ' '))
+        {
+            let value = value.trim_start_matches(|c: char| c == ':' || c == '=' || c == ' ');
+
+            default = Some(value.to_string());
+        } else {
+            parts.push(part);
+        }
+    }
+
+    let type_ = if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(", "))
+    };
+
+    (type_, default, optional)
+}
+
+// Google style arguments can have their type in parentheses, like `a (int, optional)`
+fn parse_argument_name(name: &str) -> (String, Option<String>, Option<String>, bool) {
+    let parts = name
+        .split_once('(')
+        .and_then(|(name, type_)| Some((name, type_.strip_suffix(')')?)));
+
+    match parts {
+        Some((name, type_)) => {
+            let (type_, default, optional) = parse_type(type_);
+
+            (name.trim().to_string(), type_, default, optional)
+        }
+        None => (name.to_string(), None, None, false),
+    }
+}
+
+// the first colon outside of brackets separates the name from the description
+fn find_separator(line: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ':' if depth <= 0 => return Some(index),
+            _ => {}
+        }
+    }
+
+    None
 }
 
 fn parse_arguments(docstring: &str) -> Vec<Argument> {
@@ -51,15 +127,18 @@ fn parse_arguments(docstring: &str) -> Vec<Argument> {
                 arguments.push(argument);
             }
 
-            // start a new argument
-            let mut parts = line.splitn(2, ':');
-            let name = parts.next().unwrap().trim().to_string();
+            // start a new argument, the type can contain colons too
+            let (name, description) = match find_separator(line) {
+                Some(index) => (&line[..index], Some(line[index + 1..].trim().to_string())),
+                None => (line, None),
+            };
+            let (name, type_, default, optional) = parse_argument_name(name.trim());
 
-            let description = parts.next().map(|s| s.trim().to_string());
             current_argument = Some(Argument {
                 name,
-                type_: None,
-                default: None,
+                type_,
+                default,
+                optional,
                 description,
             });
         } else {
@@ -138,34 +217,338 @@ fn parse_raises(docstring: &str) -> Vec<Raises> {
     raises
 }
 
-impl Docstring {
-    pub fn new_from_string(docstring: &str) -> Self {
-        let cleaned_docstring = cleandoc::cleandoc(docstring);
+// NumPy style entries start with an unindented header line, like `name : type`
+// or `ValueError`, followed by an indented description
+fn parse_numpy_entries(section: &str) -> Vec<(String, Option<String>)> {
+    let ds = textwrap::dedent(section);
+
+    let mut entries: Vec<(String, Option<String>)> = Vec::new();
+
+    for line in ds.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if !line.starts_with(char::is_whitespace) {
+            entries.push((line.trim().to_string(), None));
+        } else if let Some((_, description)) = entries.last_mut() {
+            let text = description.take().unwrap_or_else(|| "".to_string());
+            *description = Some((text + " " + line.trim()).trim().to_owned());
+        }
+    }
+
+    entries
+}
+
+fn parse_numpy_arguments(section: &str) -> Vec<Argument> {
+    let mut arguments = Vec::new();
+
+    for (header, description) in parse_numpy_entries(section) {
+        let (names, (type_, default, optional)) = match header.split_once(':') {
+            Some((names, type_)) => (names, parse_type(type_)),
+            None => (header.as_str(), (None, None, false)),
+        };
+
+        // several arguments can share an entry, like `x1, x2 : array_like`
+        for name in names.split(',') {
+            arguments.push(Argument {
+                name: name.trim().to_string(),
+                type_: type_.clone(),
+                default: default.clone(),
+                optional,
+                description: description.clone(),
+            });
+        }
+    }
+
+    arguments
+}
+
+fn parse_numpy_raises(section: &str) -> Vec<Raises> {
+    parse_numpy_entries(section)
+        .into_iter()
+        .map(|(exception, description)| Raises {
+            exception,
+            description,
+        })
+        .collect()
+}
+
+// a single return value is written as `type` or `name : type`, its type is
+// kept apart from the description so that it's rendered like an annotation
+fn parse_numpy_returns(section: &str) -> (Option<String>, String) {
+    let mut entries = parse_numpy_entries(section);
+
+    if entries.len() == 1 {
+        let (header, description) = entries.remove(0);
+        let type_ = match header.split_once(':') {
+            Some((_, type_)) => type_.trim().to_string(),
+            None => header,
+        };
+
+        return (Some(type_), description.unwrap_or_else(|| "".to_string()));
+    }
+
+    // a tuple is returned, each of its values is described on its own line
+    let returns = entries
+        .into_iter()
+        .map(|(header, description)| match description {
+            Some(description) => format!("{}: {}", header, description),
+            None => header,
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    (None, returns)
+}
+
+const REST_PARAM_FIELDS: [&str; 6] = ["param", "parameter", "arg", "argument", "key", "keyword"];
+const REST_FIELDS: [&str; 15] = [
+    "param",
+    "parameter",
+    "arg",
+    "argument",
+    "key",
+    "keyword",
+    "type",
+    "returns",
+    "return",
+    "rtype",
+    "yields",
+    "yield",
+    "ytype",
+    "receives",
+    "raises",
+];
+
+// splits a reST field like `:param int name: description` into
+// the field name, its arguments and the description, Epytext uses
+// the same fields with a `@` marker, like `@param name: description`
+fn parse_field(line: &str, marker: char) -> Option<(&str, Vec<&str>, &str)> {
+    let (head, description) = line.strip_prefix(marker)?.split_once(':')?;
+    let mut parts = head.split_whitespace();
+
+    let field = parts.next()?;
+
+    // `raise` and `except` are aliases of `raises`
+    let field = match field {
+        "raise" | "except" | "exception" => "raises",
+        field => field,
+    };
+
+    if !REST_FIELDS.contains(&field) {
+        return None;
+    }
+
+    Some((field, parts.collect(), description.trim()))
+}
+
+// the sections of a docstring once they are parsed
+struct Sections {
+    arguments: Vec<Argument>,
+    returns: String,
+    returns_type: Option<String>,
+    yields: String,
+    yields_type: Option<String>,
+    receives: String,
+    receives_type: Option<String>,
+    raises: Vec<Raises>,
+}
+
+fn parse_fields(section: &str, marker: char) -> Sections {
+    let mut fields: Vec<String> = Vec::new();
+
+    // join the descriptions that continue on the following indented lines,
+    // every other line starts a new entry, unknown fields and prose after
+    // the field list are dropped below
+    for line in section.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let is_continuation = line.starts_with(char::is_whitespace)
+            && !line.trim_start().starts_with(marker)
+            && !fields.is_empty();
+
+        if is_continuation {
+            let field = fields.last_mut().unwrap();
+
+            field.push(' ');
+            field.push_str(line.trim());
+        } else {
+            fields.push(line.trim().to_string());
+        }
+    }
+
+    let mut arguments: Vec<Argument> = Vec::new();
+    let mut types = Vec::new();
+    let mut returns = None;
+    let mut return_type = None;
+    let mut yields = None;
+    let mut yield_type = None;
+    let mut receives = None;
+    let mut raises = Vec::new();
+
+    for field in &fields {
+        let (field, parts, description) = match parse_field(field, marker) {
+            Some(field) => field,
+            None => continue,
+        };
+
+        let description = if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        };
+
+        match (field, parts.as_slice()) {
+            // `:param int name:` is a shortcut for a separate `:type name: int`
+            (field, [.., name]) if REST_PARAM_FIELDS.contains(&field) => {
+                let type_ = parts[..parts.len() - 1].join(" ");
+
+                arguments.push(Argument {
+                    name: name.to_string(),
+                    type_: if type_.is_empty() { None } else { Some(type_) },
+                    default: None,
+                    optional: false,
+                    description,
+                });
+            }
+            ("type", [name]) => types.push((name.to_string(), description)),
+            ("returns" | "return", _) => returns = description,
+            ("rtype", _) => return_type = description,
+            ("yields" | "yield", _) => yields = description,
+            ("ytype", _) => yield_type = description,
+            ("receives", _) => receives = description,
+            ("raises", exceptions) if !exceptions.is_empty() => raises.push(Raises {
+                exception: exceptions.join(" "),
+                description,
+            }),
+            _ => {}
+        }
+    }
+
+    for (name, type_) in types {
+        if let Some(argument) = arguments.iter_mut().find(|argument| argument.name == name) {
+            argument.type_ = type_;
+        }
+    }
+
+    Sections {
+        arguments,
+        returns: returns.unwrap_or_else(|| "".to_string()),
+        returns_type: return_type,
+        yields: yields.unwrap_or_else(|| "".to_string()),
+        yields_type: yield_type,
+        receives: receives.unwrap_or_else(|| "".to_string()),
+        receives_type: None,
+        raises,
+    }
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+
+    !line.is_empty() && line.chars().all(|c| c == '-')
+}
+
+fn numpy_section(title: &str) -> Option<&'static str> {
+    match title.trim() {
+        "Parameters" | "Other Parameters" => Some("arguments"),
+        "Returns" => Some("returns"),
+        "Yields" => Some("yields"),
+        "Receives" => Some("receives"),
+        "Raises" => Some("raises"),
+        "See Also" => Some("see_also"),
+        "Notes" => Some("notes"),
+        _ => None,
+    }
+}
 
-        let mut description = String::new();
-        let mut arguments = String::new();
-        let mut private_arguments = String::new();
-        let mut returns = String::new();
-        let mut raises = String::new();
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Google,
+    Numpy,
+    Rest,
+    Epytext,
+}
+
+// splits the docstring in its title, body and sections, only the
+// section headers of the given style are recognised
+fn parse_docstring(docstring: &str, style: Style) -> Docstring {
+    let cleaned_docstring = cleandoc::cleandoc(docstring);
+
+    let mut description = String::new();
+    let mut arguments = String::new();
+    let mut private_arguments = String::new();
+    let mut returns = String::new();
+    let mut yields = String::new();
+    let mut receives = String::new();
+    let mut raises = String::new();
+    let mut see_also = String::new();
+    let mut notes = String::new();
+    let mut fields = String::new();
+
+    let mut current_section = &mut description;
+
+    let field_marker = match style {
+        Style::Rest => Some(':'),
+        Style::Epytext => Some('@'),
+        _ => None,
+    };
+
+    let mut current_section_type = "body";
 
-        let mut current_section = &mut description;
+    let mut lines = cleaned_docstring.lines();
 
-        let mut current_section_type = "body";
+    // the title starts with the first line and ends when the first empty line is encountered
+    // pop lines until the first empty line is encountered
+    let title = lines
+        .by_ref()
+        .take_while(|line| !line.trim().is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
 
-        let mut lines = cleaned_docstring.lines();
+    let mut body: Vec<BodyPart> = Vec::new();
+    let mut current_body_part: Option<BodyPart> = None;
+
+    let mut lines = lines.peekable();
+
+    while let Some(line) = lines.next() {
+        // NumPy style sections are underlined with dashes instead of ending with a colon
+        if style == Style::Numpy && matches!(lines.peek(), Some(next) if is_underline(next)) {
+            // skip the underline
+            lines.next();
+
+            if let Some(section_type) = numpy_section(line) {
+                current_section_type = section_type;
+                current_section = match section_type {
+                    "arguments" => &mut arguments,
+                    "returns" => &mut returns,
+                    "yields" => &mut yields,
+                    "receives" => &mut receives,
+                    "raises" => &mut raises,
+                    "see_also" => &mut see_also,
+                    _ => &mut notes,
+                };
+
+                continue;
+            }
 
-        // the title starts with the first line and ends when the first empty line is encountered
-        // pop lines until the first empty line is encountered
-        let title = lines
-            .by_ref()
-            .take_while(|line| !line.trim().is_empty())
-            .collect::<Vec<&str>>()
-            .join(" ");
+            // other sections, like `Examples`, are rendered with the body
+            current_section_type = "body";
+            current_section = &mut description;
+        }
 
-        let mut body: Vec<BodyPart> = Vec::new();
-        let mut current_body_part: Option<BodyPart> = None;
+        // field lists don't have a header, they start with the first field
+        if current_section_type != "fields"
+            && matches!(field_marker, Some(marker) if parse_field(line, marker).is_some())
+        {
+            current_section_type = "fields";
+            current_section = &mut fields;
+        }
 
-        for line in lines {
+        if style == Style::Google {
             if line.starts_with("Args:") || line.starts_with("Arguments:") {
                 current_section_type = "arguments";
                 current_section = &mut arguments;
@@ -178,80 +561,261 @@ impl Docstring {
                 current_section_type = "returns";
                 current_section = &mut returns;
                 continue;
+            } else if line.starts_with("Yields:") {
+                current_section_type = "yields";
+                current_section = &mut yields;
+                continue;
+            } else if line.starts_with("Receives:") {
+                current_section_type = "receives";
+                current_section = &mut receives;
+                continue;
             } else if line.starts_with("Raises:") {
                 current_section_type = "raises";
                 current_section = &mut raises;
                 continue;
             }
+        }
 
-            if current_section_type == "body" {
-                if line.starts_with(">>> ") {
-                    // if we have a part and it's a CodeSnippet we can append to it, otherwise we need to create a new one
+        if current_section_type == "body" {
+            if line.starts_with(">>> ") {
+                // if we have a part and it's a CodeSnippet we can append to it, otherwise we need to create a new one
 
-                    let code = &line[4..];
+                let code = &line[4..];
 
-                    match current_body_part {
-                        Some(BodyPart::CodeSnippet(ref mut snippet)) => {
-                            snippet.push('\n');
-                            snippet.push_str(code);
+                match current_body_part {
+                    Some(BodyPart::CodeSnippet(ref mut snippet)) => {
+                        snippet.push('\n');
+                        snippet.push_str(code);
+                    }
+                    Some(BodyPart::Text(part)) => {
+                        if !part.trim().is_empty() {
+                            body.push(BodyPart::Text(part));
                         }
-                        Some(BodyPart::Text(part)) => {
-                            if !part.trim().is_empty() {
-                                body.push(BodyPart::Text(part));
-                            }
 
-                            current_body_part = Some(BodyPart::CodeSnippet(code.to_string()));
-                        }
-                        None => {
-                            current_body_part = Some(BodyPart::CodeSnippet(code.to_string()));
-                        }
+                        current_body_part = Some(BodyPart::CodeSnippet(code.to_string()));
                     }
-                } else {
-                    match current_body_part {
-                        Some(BodyPart::Text(ref mut text)) => {
-                            text.push('\n');
-                            text.push_str(line);
-                        }
-                        Some(part) => {
-                            body.push(part);
-                            current_body_part = Some(BodyPart::Text(line.to_string()));
-                        }
-                        None => {
-                            current_body_part = Some(BodyPart::Text(line.to_string()));
-                        }
+                    None => {
+                        current_body_part = Some(BodyPart::CodeSnippet(code.to_string()));
+                    }
+                }
+            } else {
+                match current_body_part {
+                    Some(BodyPart::Text(ref mut text)) => {
+                        text.push('\n');
+                        text.push_str(line);
+                    }
+                    Some(part) => {
+                        body.push(part);
+                        current_body_part = Some(BodyPart::Text(line.to_string()));
+                    }
+                    None => {
+                        current_body_part = Some(BodyPart::Text(line.to_string()));
                     }
                 }
             }
-
-            current_section.push_str(line);
-            current_section.push('\n')
         }
 
-        if let Some(part) = current_body_part {
-            body.push(part);
+        current_section.push_str(line);
+        current_section.push('\n')
+    }
+
+    if let Some(part) = current_body_part {
+        body.push(part);
+    }
+
+    let sections = match (style, field_marker) {
+        (Style::Numpy, _) => {
+            // yields and receives are written like the return value
+            let (returns_type, returns) = parse_numpy_returns(&returns);
+            let (yields_type, yields) = parse_numpy_returns(&yields);
+            let (receives_type, receives) = parse_numpy_returns(&receives);
+
+            Sections {
+                arguments: parse_numpy_arguments(&arguments),
+                returns,
+                returns_type,
+                yields,
+                yields_type,
+                receives,
+                receives_type,
+                raises: parse_numpy_raises(&raises),
+            }
         }
+        (_, Some(marker)) => parse_fields(&fields, marker),
+        _ => Sections {
+            arguments: parse_arguments(&arguments),
+            returns: textwrap::dedent(&returns).trim().to_string(),
+            returns_type: None,
+            yields: textwrap::dedent(&yields).trim().to_string(),
+            yields_type: None,
+            receives: textwrap::dedent(&receives).trim().to_string(),
+            receives_type: None,
+            raises: parse_raises(&raises),
+        },
+    };
+    let private_arguments = parse_arguments(&private_arguments);
+
+    Docstring {
+        title,
+        description: description.trim().to_string(),
+        arguments: sections.arguments,
+        private_arguments,
+        body,
+        returns: sections.returns,
+        returns_type: sections.returns_type,
+        yields: sections.yields,
+        yields_type: sections.yields_type,
+        receives: sections.receives,
+        receives_type: sections.receives_type,
+        raises: sections.raises,
+        see_also: textwrap::dedent(&see_also).trim().to_string(),
+        notes: textwrap::dedent(&notes).trim().to_string(),
+    }
+}
+
+/// A docstring dialect, like the Google or the NumPy style.
+pub trait DocstringParser {
+    /// The name used to force this style, for example `google`.
+    fn name(&self) -> &str;
+
+    /// Whether the docstring looks like it's written in this style.
+    fn detect(&self, docstring: &str) -> bool;
 
-        let arguments = parse_arguments(&arguments);
-        let raises = parse_raises(&raises);
-        let private_arguments = parse_arguments(&private_arguments);
+    fn parse(&self, docstring: &str) -> Docstring;
+}
+
+/// Sections ending with a colon, like `Args:` and `Returns:`,
+/// this is also the fallback when no other style is detected.
+pub struct GoogleParser;
 
+impl DocstringParser for GoogleParser {
+    fn name(&self) -> &str {
+        "google"
+    }
+
+    fn detect(&self, _docstring: &str) -> bool {
+        true
+    }
+
+    fn parse(&self, docstring: &str) -> Docstring {
+        parse_docstring(docstring, Style::Google)
+    }
+}
+
+/// Sections underlined with dashes, like `Parameters` and `Returns`.
+pub struct NumpyParser;
+
+impl DocstringParser for NumpyParser {
+    fn name(&self) -> &str {
+        "numpy"
+    }
+
+    fn detect(&self, docstring: &str) -> bool {
+        let lines = docstring.lines().collect::<Vec<&str>>();
+
+        lines
+            .windows(2)
+            .any(|pair| numpy_section(pair[0]).is_some() && is_underline(pair[1]))
+    }
+
+    fn parse(&self, docstring: &str) -> Docstring {
+        parse_docstring(docstring, Style::Numpy)
+    }
+}
+
+/// Sphinx field lists, like `:param name:` and `:rtype:`.
+pub struct RestParser;
+
+impl DocstringParser for RestParser {
+    fn name(&self) -> &str {
+        "rest"
+    }
+
+    fn detect(&self, docstring: &str) -> bool {
+        docstring
+            .lines()
+            .any(|line| parse_field(line.trim_start(), ':').is_some())
+    }
+
+    fn parse(&self, docstring: &str) -> Docstring {
+        parse_docstring(docstring, Style::Rest)
+    }
+}
+
+/// Epytext fields, like `@param name:` and `@rtype:`.
+pub struct EpytextParser;
+
+impl DocstringParser for EpytextParser {
+    fn name(&self) -> &str {
+        "epytext"
+    }
+
+    fn detect(&self, docstring: &str) -> bool {
+        docstring
+            .lines()
+            .any(|line| parse_field(line.trim_start(), '@').is_some())
+    }
+
+    fn parse(&self, docstring: &str) -> Docstring {
+        parse_docstring(docstring, Style::Epytext)
+    }
+}
+
+/// The docstring styles known to the extractor, custom parsers
+/// registered with `register` are tried before the built-in ones.
+pub struct DocstringParsers {
+    parsers: Vec<Box<dyn DocstringParser>>,
+}
+
+impl Default for DocstringParsers {
+    fn default() -> Self {
         Self {
-            title,
-            description: description.trim().to_string(),
-            arguments,
-            private_arguments,
-            body,
-            returns: textwrap::dedent(&returns).trim().to_string(),
-            raises,
+            // google detects every docstring, so it has to be the last one
+            parsers: vec![
+                Box::new(NumpyParser),
+                Box::new(RestParser),
+                Box::new(EpytextParser),
+                Box::new(GoogleParser),
+            ],
         }
     }
 }
 
+impl DocstringParsers {
+    pub fn register(&mut self, parser: Box<dyn DocstringParser>) {
+        self.parsers.insert(0, parser);
+    }
+
+    /// Whether one of the parsers is named `style`.
+    pub fn has_style(&self, style: &str) -> bool {
+        self.parsers.iter().any(|parser| parser.name() == style)
+    }
+
+    /// Parses the docstring with the parser named `style`, or with the first
+    /// parser that detects the docstring when no style is forced or it's unknown.
+    pub fn parse(&self, docstring: &str, style: Option<&str>) -> Docstring {
+        let forced =
+            style.and_then(|style| self.parsers.iter().find(|parser| parser.name() == style));
+        let parser = forced.or_else(|| self.parsers.iter().find(|parser| parser.detect(docstring)));
+
+        match parser {
+            Some(parser) => parser.parse(docstring),
+            None => GoogleParser.parse(docstring),
+        }
+    }
+}
+
+impl Docstring {
+    pub fn new_from_string(docstring: &str) -> Self {
+        DocstringParsers::default().parse(docstring, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
 
-    use crate::docstrings::BodyPart;
+    use crate::docstrings::{BodyPart, DocstringParser};
 
     #[test]
     fn it_parses_docstrings() {
@@ -362,4 +926,390 @@ mod tests {
             BodyPart::CodeSnippet("1 + 1 = 3\n2 + 2 = 5\nprint(\"something wrong\")".to_string())
         );
     }
+
+    #[test]
+    fn it_parses_numpy_docstrings() {
+        let docstring = r#"
+        Fetches rows from a Smalltable.
+
+        Retrieves rows pertaining to the given keys.
+
+        Parameters
+        ----------
+        table_handle : smalltable.Table
+            An open smalltable.Table instance.
+        keys : Sequence[str]
+            A sequence of strings representing the key of each table
+            row to fetch: String keys will be UTF-8 encoded.
+        require_all_keys : bool, optional
+            If True only rows with values set for all keys will be returned.
+        limit : int, default: 10
+            The maximum number of rows.
+        *args
+            Ignored.
+        factory : defaultdict
+            Not a default value.
+        x1, x2 : array_like
+            The coordinates.
+
+        Returns
+        -------
+        dict
+            A dict mapping keys to the corresponding table row data.
+
+        Raises
+        ------
+        IOError
+            An error occurred accessing the smalltable.
+
+        See Also
+        --------
+        fetch_smalltable_columns : Fetches columns instead.
+
+        Notes
+        -----
+        Rows are fetched in a single request.
+        "#;
+
+        let parsed_docstring = super::Docstring::new_from_string(docstring);
+
+        assert_eq!(parsed_docstring.title, "Fetches rows from a Smalltable.");
+        assert_eq!(
+            parsed_docstring.description,
+            "Retrieves rows pertaining to the given keys."
+        );
+
+        let arguments = &parsed_docstring.arguments;
+
+        assert_eq!(arguments.len(), 8);
+        assert_eq!(arguments[0].name, "table_handle");
+        assert_eq!(arguments[0].type_, Some("smalltable.Table".to_string()));
+        assert_eq!(
+            arguments[0].description,
+            Some("An open smalltable.Table instance.".to_string())
+        );
+        assert_eq!(arguments[1].name, "keys");
+        assert_eq!(arguments[1].type_, Some("Sequence[str]".to_string()));
+        assert_eq!(arguments[1].description, Some("A sequence of strings representing the key of each table row to fetch: String keys will be UTF-8 encoded.".to_string()));
+        assert_eq!(arguments[2].name, "require_all_keys");
+        assert_eq!(arguments[2].type_, Some("bool".to_string()));
+        assert_eq!(arguments[2].default, None);
+        assert_eq!(arguments[3].name, "limit");
+        assert_eq!(arguments[3].type_, Some("int".to_string()));
+        assert_eq!(arguments[3].default, Some("10".to_string()));
+        assert_eq!(arguments[4].name, "*args");
+        assert_eq!(arguments[4].type_, None);
+        assert_eq!(arguments[4].description, Some("Ignored.".to_string()));
+        assert_eq!(arguments[5].name, "factory");
+        assert_eq!(arguments[5].type_, Some("defaultdict".to_string()));
+        assert_eq!(arguments[5].default, None);
+        assert_eq!(arguments[6].name, "x1");
+        assert_eq!(arguments[7].name, "x2");
+        assert_eq!(arguments[7].type_, Some("array_like".to_string()));
+        assert_eq!(
+            arguments[7].description,
+            Some("The coordinates.".to_string())
+        );
+
+        assert_eq!(
+            parsed_docstring.returns,
+            "A dict mapping keys to the corresponding table row data."
+        );
+        assert_eq!(parsed_docstring.returns_type, Some("dict".to_string()));
+
+        assert_eq!(parsed_docstring.raises.len(), 1);
+        assert_eq!(parsed_docstring.raises[0].exception, "IOError");
+        assert_eq!(
+            parsed_docstring.raises[0].description,
+            Some("An error occurred accessing the smalltable.".to_string())
+        );
+
+        assert_eq!(
+            parsed_docstring.see_also,
+            "fetch_smalltable_columns : Fetches columns instead."
+        );
+        assert_eq!(
+            parsed_docstring.notes,
+            "Rows are fetched in a single request."
+        );
+    }
+
+    #[test]
+    fn it_ends_numpy_sections_at_unknown_titles() {
+        let docstring = r#"
+        Fetches rows from a Smalltable.
+
+        Returns
+        -------
+        dict
+            The rows.
+
+        Examples
+        --------
+        >>> fetch_smalltable_rows(table, keys)
+        "#;
+
+        let parsed_docstring = super::Docstring::new_from_string(docstring);
+
+        assert_eq!(parsed_docstring.returns, "The rows.");
+        assert_eq!(parsed_docstring.returns_type, Some("dict".to_string()));
+        assert_eq!(
+            parsed_docstring.body,
+            vec![
+                BodyPart::Text("Examples".to_string()),
+                BodyPart::CodeSnippet("fetch_smalltable_rows(table, keys)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_rest_docstrings() {
+        let docstring = r#"
+        Fetches rows from a Smalltable.
+
+        Retrieves rows pertaining to the given keys.
+
+        :param table_handle: An open smalltable.Table instance.
+        :type table_handle: smalltable.Table
+        :param Sequence[str] keys: A sequence of strings representing the key
+            of each table row to fetch.
+        :param require_all_keys: If True only rows with values set for all keys
+            will be returned.
+        :returns: A dict mapping keys to the corresponding table row data.
+        :rtype: dict
+        :raises IOError: An error occurred accessing the smalltable.
+        "#;
+
+        let parsed_docstring = super::Docstring::new_from_string(docstring);
+
+        assert_eq!(parsed_docstring.title, "Fetches rows from a Smalltable.");
+        assert_eq!(
+            parsed_docstring.description,
+            "Retrieves rows pertaining to the given keys."
+        );
+
+        let arguments = &parsed_docstring.arguments;
+
+        assert_eq!(arguments.len(), 3);
+        assert_eq!(arguments[0].name, "table_handle");
+        assert_eq!(arguments[0].type_, Some("smalltable.Table".to_string()));
+        assert_eq!(
+            arguments[0].description,
+            Some("An open smalltable.Table instance.".to_string())
+        );
+        assert_eq!(arguments[1].name, "keys");
+        assert_eq!(arguments[1].type_, Some("Sequence[str]".to_string()));
+        assert_eq!(
+            arguments[1].description,
+            Some(
+                "A sequence of strings representing the key of each table row to fetch."
+                    .to_string()
+            )
+        );
+        assert_eq!(arguments[2].name, "require_all_keys");
+        assert_eq!(arguments[2].type_, None);
+
+        assert_eq!(
+            parsed_docstring.returns,
+            "A dict mapping keys to the corresponding table row data."
+        );
+        assert_eq!(parsed_docstring.returns_type, Some("dict".to_string()));
+
+        assert_eq!(parsed_docstring.raises.len(), 1);
+        assert_eq!(parsed_docstring.raises[0].exception, "IOError");
+        assert_eq!(
+            parsed_docstring.raises[0].description,
+            Some("An error occurred accessing the smalltable.".to_string())
+        );
+    }
+
+    #[test]
+    fn it_ignores_unknown_rest_fields() {
+        let docstring = r#"
+        Creates a user.
+
+        :param name: The name.
+        :meta private:
+        :param age: The age,
+            in years.
+        :ivar id: The identifier.
+        Created users are active.
+        "#;
+
+        let parsed_docstring = super::Docstring::new_from_string(docstring);
+
+        let arguments = &parsed_docstring.arguments;
+
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(arguments[0].description, Some("The name.".to_string()));
+        assert_eq!(
+            arguments[1].description,
+            Some("The age, in years.".to_string())
+        );
+    }
+
+    #[test]
+    fn it_parses_epytext_docstrings() {
+        let docstring = r#"
+        Fetches rows from a Smalltable.
+
+        @param table_handle: An open smalltable.Table instance.
+        @type table_handle: smalltable.Table
+        @return: A dict mapping keys to the corresponding table row data.
+        @rtype: dict
+        @raise IOError: An error occurred accessing the smalltable.
+        "#;
+
+        let parsed_docstring = super::Docstring::new_from_string(docstring);
+
+        assert_eq!(parsed_docstring.description, "");
+        assert_eq!(parsed_docstring.arguments.len(), 1);
+        assert_eq!(parsed_docstring.arguments[0].name, "table_handle");
+        assert_eq!(
+            parsed_docstring.arguments[0].type_,
+            Some("smalltable.Table".to_string())
+        );
+        assert_eq!(
+            parsed_docstring.returns,
+            "A dict mapping keys to the corresponding table row data."
+        );
+        assert_eq!(parsed_docstring.returns_type, Some("dict".to_string()));
+        assert_eq!(parsed_docstring.raises[0].exception, "IOError");
+    }
+
+    struct ShoutingParser;
+
+    impl DocstringParser for ShoutingParser {
+        fn name(&self) -> &str {
+            "shouting"
+        }
+
+        fn detect(&self, docstring: &str) -> bool {
+            docstring.trim() == docstring.trim().to_uppercase()
+        }
+
+        fn parse(&self, docstring: &str) -> super::Docstring {
+            let mut parsed_docstring = super::GoogleParser.parse(docstring);
+
+            parsed_docstring.title = parsed_docstring.title.to_lowercase();
+            parsed_docstring
+        }
+    }
+
+    #[test]
+    fn it_detects_or_forces_docstring_styles() {
+        let docstring = r#"
+        Adds one.
+
+        :param a: The input value.
+        "#;
+
+        let mut parsers = super::DocstringParsers::default();
+
+        let parsed_docstring = parsers.parse(docstring, None);
+
+        assert_eq!(parsed_docstring.arguments.len(), 1);
+        assert_eq!(parsed_docstring.description, "");
+
+        let parsed_docstring = parsers.parse(docstring, Some("google"));
+
+        assert_eq!(parsed_docstring.arguments.len(), 0);
+        assert_eq!(parsed_docstring.description, ":param a: The input value.");
+
+        // an unknown style falls back to detection
+        assert!(!parsers.has_style("numpi"));
+        assert_eq!(parsers.parse(docstring, Some("numpi")).arguments.len(), 1);
+
+        parsers.register(Box::new(ShoutingParser));
+
+        assert_eq!(parsers.parse("ADDS ONE.", None).title, "adds one.");
+        assert_eq!(parsers.parse("Adds one.", None).title, "Adds one.");
+        assert_eq!(
+            parsers.parse("Adds one.", Some("shouting")).title,
+            "adds one."
+        );
+    }
+
+    #[test]
+    fn it_parses_google_argument_types() {
+        let docstring = r#"
+        Adds two numbers.
+
+        Args:
+            a (int): The first number.
+            b (Dict[str, int], optional): The second number.
+            c (str, default: "c"): A label.
+            d: Untyped.
+            e (defaultdict): A factory.
+        "#;
+
+        let arguments = super::Docstring::new_from_string(docstring).arguments;
+
+        assert_eq!(arguments.len(), 5);
+        assert_eq!(arguments[0].name, "a");
+        assert_eq!(arguments[0].type_, Some("int".to_string()));
+        assert!(!arguments[0].optional);
+        assert_eq!(
+            arguments[0].description,
+            Some("The first number.".to_string())
+        );
+        assert_eq!(arguments[1].name, "b");
+        assert_eq!(arguments[1].type_, Some("Dict[str, int]".to_string()));
+        assert!(arguments[1].optional);
+        assert_eq!(arguments[1].default, None);
+        assert_eq!(arguments[2].name, "c");
+        assert_eq!(arguments[2].type_, Some("str".to_string()));
+        assert_eq!(arguments[2].default, Some("\"c\"".to_string()));
+        assert_eq!(arguments[3].name, "d");
+        assert_eq!(arguments[3].type_, None);
+        assert_eq!(arguments[4].name, "e");
+        assert_eq!(arguments[4].type_, Some("defaultdict".to_string()));
+        assert_eq!(arguments[4].default, None);
+    }
+
+    #[test]
+    fn it_parses_yields_and_receives() {
+        let docstring = r#"
+        Counts forever.
+
+        Yields
+        ------
+        int
+            The next number.
+
+        Receives
+        --------
+        str
+            A label for the next number.
+        "#;
+
+        let parsed_docstring = super::Docstring::new_from_string(docstring);
+
+        assert_eq!(parsed_docstring.yields, "The next number.");
+        assert_eq!(parsed_docstring.yields_type, Some("int".to_string()));
+        assert_eq!(parsed_docstring.receives, "A label for the next number.");
+        assert_eq!(parsed_docstring.receives_type, Some("str".to_string()));
+        assert_eq!(parsed_docstring.returns, "");
+        assert_eq!(parsed_docstring.returns_type, None);
+
+        let docstring = r#"
+        Counts forever.
+
+        :param start: The first number.
+        :yields: The next number.
+        :ytype: int
+        :receives: A label for the next number.
+        "#;
+
+        let parsed_docstring = super::Docstring::new_from_string(docstring);
+
+        assert_eq!(
+            parsed_docstring.arguments[0].description,
+            Some("The first number.".to_string())
+        );
+        assert_eq!(parsed_docstring.yields, "The next number.");
+        assert_eq!(parsed_docstring.yields_type, Some("int".to_string()));
+        assert_eq!(parsed_docstring.receives, "A label for the next number.");
+        assert_eq!(parsed_docstring.returns, "");
+    }
 }
@@ -1,40 +1,363 @@
 use crate::docstrings;
-use rustpython_ast::{ArgData, Arguments, Constant, ExprKind, Located, StmtKind};
+use crate::signature;
+use crate::strawberry;
+use rustpython_ast::{
+    ArgData, Arguments, Constant, ExcepthandlerKind, Expr, ExprKind, Keyword, Located, Operator,
+    StmtKind,
+};
 use rustpython_parser::parser;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceLocation {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+fn source_location(path: &str, statement: &Located<StmtKind>) -> SourceLocation {
+    let start_line = statement.location.row();
+
+    SourceLocation {
+        path: path.to_string(),
+        start_line,
+        end_line: match &statement.end_location {
+            Some(end_location) => end_location.row(),
+            None => start_line,
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgumentKind {
+    PositionalOnly,
+    PositionalOrKeyword,
+    VarPositional,
+    KeywordOnly,
+    VarKeyword,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecoratorKind {
+    Property,
+    StaticMethod,
+    ClassMethod,
+    AbstractMethod,
+    Overload,
+    #[serde(rename = "cached_property")]
+    CachedProperty,
+    Deprecated,
+}
+
+impl DecoratorKind {
+    fn from_decorator(decorator: &str) -> Option<Self> {
+        // we only look at the last part of the name, so that both `overload`
+        // and `typing.overload` are recognised, and ignore any call arguments
+        let name = decorator.split('(').next().unwrap_or(decorator);
+        let name = name.rsplit('.').next().unwrap_or(name).trim();
+
+        match name {
+            "property" => Some(DecoratorKind::Property),
+            "staticmethod" => Some(DecoratorKind::StaticMethod),
+            "classmethod" => Some(DecoratorKind::ClassMethod),
+            "abstractmethod" => Some(DecoratorKind::AbstractMethod),
+            "overload" => Some(DecoratorKind::Overload),
+            "cached_property" => Some(DecoratorKind::CachedProperty),
+            "deprecated" => Some(DecoratorKind::Deprecated),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Serialize)]
 pub struct Argument {
     pub name: String,
+    pub kind: ArgumentKind,
+    /// The annotation in the signature, this is what `signature` is built from
+    pub annotation: Option<String>,
+    /// The annotation, or the type written in the docstring when there's none
     #[serde(rename(serialize = "type"))]
-    pub type_: String,
+    pub type_: Option<String>,
     pub default: Option<String>,
     pub description: Option<String>,
+    /// Whether the argument is listed in the docstring
+    pub documented: bool,
+    /// Where the docstring contradicts the signature, reported as warnings
+    #[serde(skip)]
+    pub conflicts: Vec<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Function {
     pub name: String,
+    pub is_async: bool,
     pub docstring: docstrings::Docstring,
+    pub returns: Option<String>,
+    /// The element type of a `Generator[...]` or `Iterator[...]` return annotation
+    pub yields: Option<String>,
+    /// The send type of a `Generator[...]` return annotation
+    pub receives: Option<String>,
+    pub signature: String,
+    pub decorators: Vec<String>,
+    pub decorator_kinds: Vec<DecoratorKind>,
+    pub overloads: Vec<String>,
+    pub guard: Option<String>,
+    pub location: SourceLocation,
     pub arguments: Vec<Argument>,
     pub private_arguments: Vec<Argument>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct Attribute {
+    pub name: String,
+    #[serde(rename(serialize = "type"))]
+    pub type_: Option<String>,
+    pub default: Option<String>,
+    pub docstring: docstrings::Docstring,
+    /// Only set for the keys of a TypedDict
+    pub required: Option<bool>,
+    pub location: SourceLocation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableKind {
+    Constant,
+    Variable,
+    TypeAlias,
+    TypeVar,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Variable {
+    pub name: String,
+    pub kind: VariableKind,
+    #[serde(rename(serialize = "type"))]
+    pub type_: Option<String>,
+    pub default: Option<String>,
+    pub docstring: docstrings::Docstring,
+    pub guard: Option<String>,
+    pub location: SourceLocation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnumMember {
+    pub name: String,
+    pub value: String,
+    pub docstring: docstrings::Docstring,
+    pub location: SourceLocation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassKind {
+    Class,
+    Enum,
+    TypedDict,
+    NamedTuple,
+    Protocol,
+}
+
+// returns the name of a type without its module and its type arguments,
+// for example `typing.Protocol[T]` becomes `Protocol`
+fn bare_type_name(type_: &str) -> &str {
+    let name = type_.split('[').next().unwrap_or(type_);
+
+    name.rsplit('.').next().unwrap_or(name).trim()
+}
+
+impl ClassKind {
+    fn from_bases(bases: &[String]) -> Self {
+        let names = bases
+            .iter()
+            .map(|base| bare_type_name(base))
+            .collect::<Vec<&str>>();
+
+        if names
+            .iter()
+            .any(|name| matches!(*name, "Enum" | "IntEnum" | "StrEnum" | "Flag" | "IntFlag"))
+        {
+            ClassKind::Enum
+        } else if names.contains(&"TypedDict") {
+            ClassKind::TypedDict
+        } else if names.contains(&"NamedTuple") {
+            ClassKind::NamedTuple
+        } else if names.contains(&"Protocol") {
+            ClassKind::Protocol
+        } else {
+            ClassKind::Class
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberKind {
+    Method,
+    Attribute,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InheritedMember {
+    pub name: String,
+    pub kind: MemberKind,
+    /// The name of the class where the member is defined
+    pub defined_in: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Class {
+    pub name: String,
+    pub kind: ClassKind,
+    pub docstring: docstrings::Docstring,
+    pub guard: Option<String>,
+    pub location: SourceLocation,
+    pub bases: Vec<String>,
+    /// The `total=` flag of a TypedDict
+    pub total: Option<bool>,
+    pub members: Vec<EnumMember>,
+    pub attributes: Vec<Attribute>,
+    pub methods: Vec<Function>,
+    pub inherited_members: Vec<InheritedMember>,
+    /// The GraphQL view of the class when it is decorated with Strawberry
+    pub graphql: Option<strawberry::GraphQLType>,
+}
+
 pub struct Module {
     // pub name: String,
-    // pub docstring: docstrings::Docstring,
+    pub docstring: docstrings::Docstring,
+    pub all: Option<Vec<String>>,
+    pub variables: Vec<Variable>,
     pub functions: Vec<Function>,
+    pub classes: Vec<Class>,
 }
 
-fn extract_function(
-    name: String,
-    body: Vec<Located<StmtKind>>,
-    arguments: Box<Arguments>,
-) -> Function {
-    // find docstring, the first statement in the body of the function
+/// A problem in the documented code that doesn't prevent generating the docs.
+#[derive(Debug, Serialize)]
+pub struct Warning {
+    pub message: String,
+    pub location: SourceLocation,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.location.path.is_empty() {
+            write!(f, "line {}: {}", self.location.start_line, self.message)
+        } else {
+            write!(
+                f,
+                "{}:{}: {}",
+                self.location.path, self.location.start_line, self.message
+            )
+        }
+    }
+}
+
+fn argument_warnings(qualified_name: &str, function: &Function) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    for argument in &function.arguments {
+        let problems = if argument.documented {
+            argument.conflicts.clone()
+        } else {
+            vec!["is not documented".to_string()]
+        };
+
+        for problem in problems {
+            warnings.push(Warning {
+                message: format!(
+                    "argument `{}` of `{}` {}",
+                    argument.name, qualified_name, problem
+                ),
+                location: function.location.clone(),
+            });
+        }
+    }
+
+    warnings
+}
+
+// when __all__ is defined it's the source of truth for the public api,
+// otherwise we follow the convention of prefixing private names with _
+fn is_public(all: &Option<Vec<String>>, name: &str) -> bool {
+    match all {
+        Some(all) => all.iter().any(|public_name| public_name == name),
+        None => !name.starts_with('_'),
+    }
+}
+
+// class members follow the underscore convention, but dunder methods
+// like __init__ are part of the public interface
+fn is_public_member(name: &str) -> bool {
+    !name.starts_with('_') || (name.starts_with("__") && name.ends_with("__"))
+}
+
+impl Module {
+    pub fn retain_public(&mut self) {
+        let all = &self.all;
+
+        self.variables
+            .retain(|variable| is_public(all, &variable.name));
+        self.functions
+            .retain(|function| is_public(all, &function.name));
+        self.classes.retain(|class| is_public(all, &class.name));
+
+        for class in self.classes.iter_mut() {
+            class
+                .attributes
+                .retain(|attribute| is_public_member(&attribute.name));
+            class
+                .methods
+                .retain(|method| is_public_member(&method.name));
+            class
+                .inherited_members
+                .retain(|member| is_public_member(&member.name));
+        }
+    }
+
+    pub fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+
+        for function in &self.functions {
+            warnings.extend(argument_warnings(&function.name, function));
+        }
+
+        for class in &self.classes {
+            for method in &class.methods {
+                let qualified_name = format!("{}.{}", class.name, method.name);
+
+                warnings.extend(argument_warnings(&qualified_name, method));
+            }
+        }
+
+        warnings
+    }
+}
+
+// the settings shared by all the extract functions
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    path: &'a str,
+    parsers: &'a docstrings::DocstringParsers,
+    style: Option<&'a str>,
+}
+
+impl Context<'_> {
+    fn parse_docstring(&self, docstring: &str) -> docstrings::Docstring {
+        self.parsers.parse(docstring, self.style)
+    }
+}
+
+fn extract_docstring(body: &[Located<StmtKind>]) -> String {
+    // find docstring, the first statement in the body
     // that's an Expr with a Constant value
 
-    let docstring_text = match body.first() {
+    match body.first() {
         Some(stmt) => match &stmt.node {
             StmtKind::Expr { value } => match &value.node {
                 ExprKind::Constant { value, kind: _ } => match value {
@@ -46,16 +369,176 @@ fn extract_function(
             _ => "".to_string(),
         },
         None => "".to_string(),
-    };
+    }
+}
+
+// ignores the formatting differences between the docstring and the code,
+// like `Dict[str,int]` and `Dict[str, int]` or `"a"` and `'a'`
+fn normalize_expression(expression: &str) -> String {
+    expression
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '"' { '\'' } else { c })
+        .collect()
+}
+
+fn find_conflicts(
+    kind: ArgumentKind,
+    annotation: Option<&str>,
+    default: Option<&str>,
+    docstring_argument: &docstrings::Argument,
+) -> Vec<String> {
+    let mut conflicts = Vec::new();
+
+    if let (Some(annotation), Some(type_)) = (annotation, &docstring_argument.type_) {
+        if normalize_expression(annotation) != normalize_expression(type_) {
+            conflicts.push(format!(
+                "is annotated as `{}` but documented as `{}`",
+                annotation, type_
+            ));
+        }
+    }
+
+    // *args and **kwargs can always be omitted
+    let is_variadic = matches!(kind, ArgumentKind::VarPositional | ArgumentKind::VarKeyword);
+
+    match (default, &docstring_argument.default) {
+        (Some(default), Some(documented_default))
+            if normalize_expression(default) != normalize_expression(documented_default) =>
+        {
+            conflicts.push(format!(
+                "defaults to `{}` but is documented with default `{}`",
+                default, documented_default
+            ));
+        }
+        (None, documented_default)
+            if !is_variadic && (docstring_argument.optional || documented_default.is_some()) =>
+        {
+            conflicts.push("is documented as optional but has no default".to_string());
+        }
+        _ => {}
+    }
 
-    let docstring = docstrings::Docstring::new_from_string(&docstring_text);
+    conflicts
+}
+
+fn extract_argument(argument: &ArgData, kind: ArgumentKind, default: Option<&Expr>) -> Argument {
+    let annotation = argument
+        .annotation
+        .as_ref()
+        .map(|annotation| annotation.to_string());
+
+    Argument {
+        name: argument.arg.to_string(),
+        kind,
+        type_: annotation.clone(),
+        annotation,
+        default: default.map(|default| default.to_string()),
+        description: None,
+        documented: false,
+        conflicts: Vec::new(),
+    }
+}
 
-    let docstring_arguments = docstring
-        .arguments
+// docstrings can refer to *args and **kwargs with or without the stars
+fn find_documented_argument<'a>(
+    documented_arguments: &'a [docstrings::Argument],
+    name: &str,
+) -> Option<&'a docstrings::Argument> {
+    documented_arguments
         .iter()
-        .map(|arg| (arg.name.clone(), arg))
-        .collect::<std::collections::HashMap<String, &docstrings::Argument>>();
+        .find(|arg| arg.name.trim_start_matches('*') == name)
+}
+
+// splits the arguments in the public and the private ones, the private ones
+// are documented in their own section, and adds what the docstring says about them
+fn document_arguments(
+    arguments: Vec<Argument>,
+    docstring: &docstrings::Docstring,
+) -> (Vec<Argument>, Vec<Argument>) {
+    let mut public_arguments = Vec::new();
+    let mut private_arguments = Vec::new();
+
+    for (index, argument) in arguments.into_iter().enumerate() {
+        if let Some(docstring_argument) =
+            find_documented_argument(&docstring.arguments, &argument.name)
+        {
+            let conflicts = find_conflicts(
+                argument.kind,
+                argument.annotation.as_deref(),
+                argument.default.as_deref(),
+                docstring_argument,
+            );
+
+            public_arguments.push(Argument {
+                // unannotated parameters fall back to the type written in the docstring, if any
+                type_: argument
+                    .annotation
+                    .clone()
+                    .or_else(|| docstring_argument.type_.clone()),
+                description: docstring_argument.description.clone(),
+                documented: true,
+                conflicts,
+                ..argument
+            });
+        } else if find_documented_argument(&docstring.private_arguments, &argument.name).is_some() {
+            private_arguments.push(Argument {
+                documented: true,
+                ..argument
+            });
+        } else if index == 0 && (argument.name == "self" || argument.name == "cls") {
+            // the implicit first argument of methods doesn't need to be documented
+        } else {
+            public_arguments.push(argument);
+        }
+    }
 
+    (public_arguments, private_arguments)
+}
+
+// the yield and send types of `Generator[Y, S, R]`, `AsyncGenerator[Y, S]`
+// and of the iterator types, which can't receive values
+fn generator_types(returns: &Expr) -> (Option<String>, Option<String>) {
+    let (value, slice) = match &returns.node {
+        ExprKind::Subscript {
+            value,
+            slice,
+            ctx: _,
+        } => (value, slice),
+        _ => return (None, None),
+    };
+
+    let type_arguments = match &slice.node {
+        ExprKind::Tuple { elts, ctx: _ } => elts
+            .iter()
+            .map(|element| element.to_string())
+            .collect::<Vec<String>>(),
+        _ => vec![slice.to_string()],
+    };
+
+    let yields = type_arguments.first().cloned();
+    // a send type of None means that the generator doesn't receive values
+    let receives = type_arguments
+        .get(1)
+        .filter(|receives| *receives != "None")
+        .cloned();
+
+    match bare_type_name(&value.to_string()) {
+        "Generator" | "AsyncGenerator" => (yields, receives),
+        "Iterator" | "Iterable" | "AsyncIterator" | "AsyncIterable" => (yields, None),
+        _ => (None, None),
+    }
+}
+
+fn extract_function(
+    name: String,
+    docstring: docstrings::Docstring,
+    arguments: Box<Arguments>,
+    returns: Option<Box<Expr>>,
+    decorator_list: Vec<Expr>,
+    is_async: bool,
+    location: SourceLocation,
+) -> Function {
     let mut function_arguments = Vec::new();
 
     // args, posonlyargs and kwonlyargs are lists of arg nodes.
@@ -64,30 +547,39 @@ fn extract_function(
     // defaults is a list of default values for arguments that can be passed positionally.
     // If there are fewer defaults, they correspond to the last n arguments.
 
-    for argument in arguments.args {
-        match argument.node {
-            ArgData {
-                arg,
-                annotation: Some(annotation),
-                ..
-            } => {
-                let name = arg.to_string();
-                let description = match docstring_arguments.get(&name) {
-                    Some(arg) => arg.description.clone(),
-                    None => None,
-                };
+    // defaults are shared between posonlyargs and args, so we need to take both into account
+    let missing_defaults =
+        arguments.posonlyargs.len() + arguments.args.len() - arguments.defaults.len();
 
-                let argument = Argument {
-                    name,
-                    type_: annotation.to_string(),
-                    default: None,
-                    description,
-                };
-                function_arguments.push(argument);
-            }
-            _ => {}
-        }
+    let positional_arguments = arguments
+        .posonlyargs
+        .iter()
+        .map(|argument| (argument, ArgumentKind::PositionalOnly))
+        .chain(
+            arguments
+                .args
+                .iter()
+                .map(|argument| (argument, ArgumentKind::PositionalOrKeyword)),
+        );
+
+    for (index, (argument, kind)) in positional_arguments.enumerate() {
+        let default = if index >= missing_defaults {
+            Some(&arguments.defaults[index - missing_defaults])
+        } else {
+            None
+        };
+
+        function_arguments.push(extract_argument(&argument.node, kind, default));
+    }
+
+    if let Some(vararg) = &arguments.vararg {
+        function_arguments.push(extract_argument(
+            &vararg.node,
+            ArgumentKind::VarPositional,
+            None,
+        ));
     }
+
     let kw_missing_defaults = arguments.kwonlyargs.len() - arguments.kw_defaults.len();
 
     for (index, argument) in arguments.kwonlyargs.iter().enumerate() {
@@ -97,148 +589,1904 @@ fn extract_function(
             None
         };
 
-        match &argument.node {
-            ArgData {
-                arg,
-                annotation: Some(annotation),
-                ..
-            } => {
-                let name = arg.to_string();
-                let description = match docstring_arguments.get(&name) {
-                    Some(arg) => arg.description.clone(),
-                    None => None,
-                };
+        function_arguments.push(extract_argument(
+            &argument.node,
+            ArgumentKind::KeywordOnly,
+            default,
+        ));
+    }
 
-                let argument = Argument {
-                    name,
-                    type_: annotation.to_string(),
-                    default: match default {
-                        Some(default) => Some(default.to_string()),
-                        None => None,
-                    },
-                    description,
-                };
-                function_arguments.push(argument);
-            }
-            _ => {}
-        }
+    if let Some(kwarg) = &arguments.kwarg {
+        function_arguments.push(extract_argument(
+            &kwarg.node,
+            ArgumentKind::VarKeyword,
+            None,
+        ));
     }
 
-    let mut public_arguments = Vec::new();
-    let mut private_arguments = Vec::new();
+    let (yields, receives) = match &returns {
+        Some(returns) => generator_types(returns),
+        None => (None, None),
+    };
 
-    // move the arguments that start with _ to the private arguments list
+    let returns = returns.map(|returns| returns.to_string());
 
-    for argument in function_arguments {
-        if docstring
-            .arguments
-            .iter()
-            .any(|arg| arg.name == argument.name)
-        {
-            public_arguments.push(argument);
-        } else if docstring
-            .private_arguments
-            .iter()
-            .any(|arg| arg.name == argument.name)
-        {
-            private_arguments.push(argument);
-        } else {
-            // TODO: missing argument in docstring
-        }
-    }
+    // the signature includes every argument, even the ones that aren't documented
+    let signature =
+        signature::format_signature(&name, &function_arguments, returns.as_deref(), is_async);
+
+    let decorators = decorator_list
+        .iter()
+        .map(|decorator| decorator.to_string())
+        .collect::<Vec<String>>();
+
+    let decorator_kinds = decorators
+        .iter()
+        .filter_map(|decorator| DecoratorKind::from_decorator(decorator))
+        .collect();
+
+    let (public_arguments, private_arguments) = document_arguments(function_arguments, &docstring);
 
     Function {
         name: name.to_string(),
+        is_async,
         docstring,
+        returns,
+        yields,
+        receives,
+        signature,
+        decorators,
+        decorator_kinds,
+        overloads: Vec::new(),
+        guard: None,
+        location,
         arguments: public_arguments,
         private_arguments,
     }
 }
 
-pub fn extract(code: &str) -> Module {
-    let python_ast = parser::parse_program(&code, "something").expect("Unable to parse");
+fn merge_overloads(mut overloads: Vec<Function>, implementation: Option<Function>) -> Function {
+    let signatures = overloads
+        .iter()
+        .map(|overload| overload.signature.clone())
+        .collect();
 
-    let mut functions = Vec::new();
+    // without an implementation, like in stubs, the first overload stands for it
+    let mut function = match implementation {
+        Some(implementation) => implementation,
+        None => overloads.remove(0),
+    };
 
-    // find all functions in ast
-    for statement in python_ast {
-        match statement.node {
-            StmtKind::FunctionDef {
-                name,
-                body,
-                args,
-                decorator_list: _,
-                returns: _,
-                type_comment: _,
-            } => {
-                functions.push(extract_function(name, body, args));
-            }
-            _ => {}
+    // the docstring comes from the implementation or, if that's not documented,
+    // from the first overload that is
+    if function.docstring.title.is_empty() {
+        if let Some(overload) = overloads
+            .into_iter()
+            .find(|overload| !overload.docstring.title.is_empty())
+        {
+            let arguments = std::mem::take(&mut function.arguments);
+            let (public_arguments, private_arguments) =
+                document_arguments(arguments, &overload.docstring);
+
+            function.arguments = public_arguments;
+            function.private_arguments.extend(private_arguments);
+            function.docstring = overload.docstring;
         }
     }
 
-    Module { functions }
+    function.overloads = signatures;
+    function
+        .decorator_kinds
+        .retain(|kind| *kind != DecoratorKind::Overload);
+    function.decorators.retain(|decorator| {
+        DecoratorKind::from_decorator(decorator) != Some(DecoratorKind::Overload)
+    });
+
+    function
 }
 
-#[cfg(test)]
-mod tests {
-    use pretty_assertions::assert_eq;
+fn group_overloads(functions: Vec<Function>) -> Vec<Function> {
+    let mut grouped = Vec::new();
+    let mut overloads: Vec<Function> = Vec::new();
 
-    #[test]
-    fn it_parses_python_function() {
-        let code = r#"
-        def foo(a: int, b: int) -> int:
-            """Example docstring
+    for function in functions {
+        // overloads of a different function, these don't have an implementation
+        if matches!(overloads.first(), Some(overload) if overload.name != function.name) {
+            grouped.push(merge_overloads(std::mem::take(&mut overloads), None));
+        }
 
-            Args:
-                a: a number
-                b: another number
-                directives:
-                    a Sequence of schema directives, this will be outputted in the schema
-                    when printing it.
+        if function.decorator_kinds.contains(&DecoratorKind::Overload) {
+            overloads.push(function);
+        } else if overloads.is_empty() {
+            grouped.push(function);
+        } else {
+            grouped.push(merge_overloads(
+                std::mem::take(&mut overloads),
+                Some(function),
+            ));
+        }
+    }
 
+    if !overloads.is_empty() {
+        grouped.push(merge_overloads(overloads, None));
+    }
 
-            Returns:
-                the sum of a and b
+    grouped
+}
 
-            Raises:
-                ValueError: if a or b are not numbers
-            """
-            return a + b
-        "#;
+// returns the assignments in the body along with their value, so that
+// callers can look at the expression that was assigned
+fn extract_assignments<'a>(
+    body: &'a [Located<StmtKind>],
+    context: &Context,
+) -> Vec<(Attribute, Option<&'a Expr>)> {
+    let mut assignments = Vec::new();
 
-        let result = super::extract(&textwrap::dedent(code));
+    for (index, statement) in body.iter().enumerate() {
+        let (targets, type_, default) = match &statement.node {
+            StmtKind::AnnAssign {
+                target,
+                annotation,
+                value,
+                simple: _,
+            } => (
+                vec![target.as_ref()],
+                Some(annotation.to_string()),
+                value.as_deref(),
+            ),
+            StmtKind::Assign {
+                targets,
+                value,
+                type_comment: _,
+            } => (targets.iter().collect(), None, Some(value.as_ref())),
+            _ => continue,
+        };
 
-        assert_eq!(result.functions.len(), 1);
+        // PEP 257 attribute docstrings are string literals right after the assignment
+        let docstring_text = extract_docstring(&body[index + 1..]);
 
-        let function = &result.functions[0];
+        for target in targets {
+            if let ExprKind::Name { id, ctx: _ } = &target.node {
+                let attribute = Attribute {
+                    name: id.to_string(),
+                    type_: type_.clone(),
+                    default: default.map(|default| default.to_string()),
+                    docstring: context.parse_docstring(&docstring_text),
+                    required: None,
+                    location: source_location(context.path, statement),
+                };
 
-        assert_eq!(function.name, "foo");
-        assert_eq!(function.docstring.title, "Example docstring");
-        assert_eq!(function.docstring.description, "");
-        assert_eq!(function.docstring.arguments.len(), 3);
+                assignments.push((attribute, default));
+            }
+        }
+    }
 
-        let arguments = &function.docstring.arguments;
+    assignments
+}
 
-        assert_eq!(arguments[0].name, "a");
+fn extract_attributes(body: &[Located<StmtKind>], context: &Context) -> Vec<Attribute> {
+    extract_assignments(body, context)
+        .into_iter()
+        .map(|(attribute, _)| attribute)
+        .collect()
+}
+
+fn is_type_expression(value: &Expr) -> bool {
+    // we consider a subscript a type when it's a generic from typing
+    // (which are capitalised) or one of the builtin generics
+    match &value.node {
+        ExprKind::Subscript {
+            value,
+            slice: _,
+            ctx: _,
+        } => {
+            let value = value.to_string();
+            let name = value.rsplit('.').next().unwrap_or(&value);
+
+            name.starts_with(|c: char| c.is_ascii_uppercase())
+                || matches!(
+                    name,
+                    "list" | "dict" | "tuple" | "set" | "frozenset" | "type"
+                )
+        }
+        _ => false,
+    }
+}
+
+fn classify_variable(attribute: &Attribute, value: Option<&Expr>) -> VariableKind {
+    if let Some(type_) = &attribute.type_ {
+        if type_ == "TypeAlias" || type_.ends_with(".TypeAlias") {
+            return VariableKind::TypeAlias;
+        }
+    }
+
+    if let Some(value) = value {
+        if let ExprKind::Call { func, .. } = &value.node {
+            let func = func.to_string();
+            let name = func.rsplit('.').next().unwrap_or(&func);
+
+            if matches!(name, "TypeVar" | "ParamSpec" | "TypeVarTuple") {
+                return VariableKind::TypeVar;
+            }
+        }
+
+        if attribute.type_.is_none() && is_type_expression(value) {
+            return VariableKind::TypeAlias;
+        }
+    }
+
+    let is_final =
+        matches!(&attribute.type_, Some(type_) if type_ == "Final" || type_.starts_with("Final["));
+
+    let is_upper_case = attribute.name.chars().any(|c| c.is_ascii_alphabetic())
+        && !attribute.name.chars().any(|c| c.is_ascii_lowercase());
+
+    if is_final || is_upper_case {
+        VariableKind::Constant
+    } else {
+        VariableKind::Variable
+    }
+}
+
+fn extract_variables(body: &[Located<StmtKind>], context: &Context) -> Vec<Variable> {
+    extract_assignments(body, context)
+        .into_iter()
+        // __all__ describes the module, it isn't something to document
+        .filter(|(attribute, _)| attribute.name != "__all__")
+        .map(|(attribute, value)| Variable {
+            kind: classify_variable(&attribute, value),
+            name: attribute.name,
+            type_: attribute.type_,
+            default: attribute.default,
+            docstring: attribute.docstring,
+            guard: None,
+            location: attribute.location,
+        })
+        .collect()
+}
+
+fn is_name(expr: &Expr, name: &str) -> bool {
+    matches!(&expr.node, ExprKind::Name { id, ctx: _ } if id == name)
+}
+
+fn extract_string_literals(value: &Expr) -> Option<Vec<String>> {
+    let elements = match &value.node {
+        ExprKind::List { elts, ctx: _ } => elts,
+        ExprKind::Tuple { elts, ctx: _ } => elts,
+        _ => return None,
+    };
+
+    elements
+        .iter()
+        .map(|element| match &element.node {
+            ExprKind::Constant {
+                value: Constant::Str(value),
+                kind: _,
+            } => Some(value.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn extract_all(body: &[Located<StmtKind>]) -> Option<Vec<String>> {
+    let mut all: Option<Vec<String>> = None;
+
+    // we only support literal lists and tuples, both assigned and added with +=
+    for statement in body {
+        match &statement.node {
+            StmtKind::Assign {
+                targets,
+                value,
+                type_comment: _,
+            } if targets.iter().any(|target| is_name(target, "__all__")) => {
+                all = extract_string_literals(value);
+            }
+            StmtKind::AnnAssign {
+                target,
+                annotation: _,
+                value: Some(value),
+                simple: _,
+            } if is_name(target, "__all__") => {
+                all = extract_string_literals(value);
+            }
+            StmtKind::AugAssign {
+                target,
+                op: Operator::Add,
+                value,
+            } if is_name(target, "__all__") => {
+                if let (Some(all), Some(names)) = (all.as_mut(), extract_string_literals(value)) {
+                    all.extend(names);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    all
+}
+
+fn extract_class(
+    name: String,
+    body: Vec<Located<StmtKind>>,
+    bases: Vec<Expr>,
+    keywords: Vec<Keyword>,
+    decorator_list: Vec<Expr>,
+    context: &Context,
+    location: SourceLocation,
+) -> Class {
+    let docstring = context.parse_docstring(&extract_docstring(&body));
+
+    let bases = bases
+        .iter()
+        .map(|base| base.to_string())
+        .collect::<Vec<String>>();
+
+    // the kind can also come from the bases defined in the module, so the
+    // members and the keys are split from the attributes in `resolve_kinds`
+    let kind = ClassKind::from_bases(&bases);
+
+    let attributes = extract_attributes(&body, context);
+
+    let total = keywords
+        .iter()
+        .find(|keyword| keyword.node.arg.as_deref() == Some("total"))
+        .map(|keyword| keyword.node.value.to_string() != "False");
+
+    let graphql = strawberry::extract_graphql_type(&name, &decorator_list, &body);
+
+    let mut methods = Vec::new();
+
+    // methods are the functions defined directly in the body of the class
+    for statement in body {
+        let location = source_location(context.path, &statement);
+
+        match statement.node {
+            StmtKind::FunctionDef {
+                name,
+                body,
+                args,
+                decorator_list,
+                returns,
+                type_comment: _,
+            } => {
+                methods.push(extract_function(
+                    name,
+                    context.parse_docstring(&extract_docstring(&body)),
+                    args,
+                    returns,
+                    decorator_list,
+                    false,
+                    location,
+                ));
+            }
+            StmtKind::AsyncFunctionDef {
+                name,
+                body,
+                args,
+                decorator_list,
+                returns,
+                type_comment: _,
+            } => {
+                methods.push(extract_function(
+                    name,
+                    context.parse_docstring(&extract_docstring(&body)),
+                    args,
+                    returns,
+                    decorator_list,
+                    true,
+                    location,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Class {
+        name,
+        kind,
+        docstring,
+        guard: None,
+        location,
+        bases,
+        total,
+        members: Vec::new(),
+        attributes,
+        methods: group_overloads(methods),
+        inherited_members: Vec::new(),
+        graphql,
+    }
+}
+
+impl Class {
+    fn set_kind(&mut self, kind: ClassKind) {
+        self.kind = kind;
+
+        match kind {
+            // in enums every assignment is a member, except for the _sunder_ and __dunder__ names
+            ClassKind::Enum => {
+                for attribute in std::mem::take(&mut self.attributes) {
+                    match attribute.default {
+                        Some(value) if !attribute.name.starts_with('_') => {
+                            self.members.push(EnumMember {
+                                name: attribute.name,
+                                value,
+                                docstring: attribute.docstring,
+                                location: attribute.location,
+                            });
+                        }
+                        default => self.attributes.push(Attribute {
+                            default,
+                            ..attribute
+                        }),
+                    }
+                }
+            }
+            // keys of a TypedDict are required depending on `total=`, unless they
+            // are explicitly wrapped in Required[...] or NotRequired[...]
+            ClassKind::TypedDict => {
+                let total = *self.total.get_or_insert(true);
+
+                for attribute in self.attributes.iter_mut() {
+                    let annotation = attribute.type_.as_deref().map(bare_type_name);
+
+                    attribute.required = match annotation {
+                        Some("Required") => Some(true),
+                        Some("NotRequired") => Some(false),
+                        _ => Some(total),
+                    };
+                }
+            }
+            _ => self.total = None,
+        }
+    }
+}
+
+// C3 linearization, the same algorithm Python uses to compute the MRO,
+// limited to the classes defined in the module
+fn linearize(
+    name: &str,
+    classes: &HashMap<&str, &Class>,
+    visiting: &mut Vec<String>,
+) -> Vec<String> {
+    let class = match classes.get(name) {
+        Some(class) => class,
+        None => return Vec::new(),
+    };
+
+    // protect against cycles, which can happen when a class is redefined
+    if visiting.iter().any(|visited| visited == name) {
+        return vec![name.to_string()];
+    }
+
+    visiting.push(name.to_string());
+
+    // bases from other modules can't be resolved, so we skip them
+    let bases = class
+        .bases
+        .iter()
+        .map(|base| base.split('[').next().unwrap_or(base).trim())
+        .filter(|base| *base != name && classes.contains_key(base))
+        .collect::<Vec<&str>>();
+
+    let mut sequences = bases
+        .iter()
+        .map(|base| linearize(base, classes, visiting))
+        .collect::<Vec<Vec<String>>>();
+
+    sequences.push(bases.iter().map(|base| base.to_string()).collect());
+
+    visiting.pop();
+
+    let mut mro = vec![name.to_string()];
+
+    loop {
+        sequences.retain(|sequence| !sequence.is_empty());
+
+        if sequences.is_empty() {
+            break;
+        }
+
+        let head = sequences
+            .iter()
+            .map(|sequence| &sequence[0])
+            .find(|candidate| {
+                !sequences
+                    .iter()
+                    .any(|sequence| sequence[1..].contains(*candidate))
+            })
+            .cloned();
+
+        match head {
+            Some(head) => {
+                for sequence in sequences.iter_mut() {
+                    if sequence[0] == head {
+                        sequence.remove(0);
+                    }
+                }
+
+                mro.push(head);
+            }
+            None => {
+                // the hierarchy is inconsistent, python would raise an error here,
+                // we fall back to a depth first order instead
+                for class_name in sequences.into_iter().flatten() {
+                    if !mro.contains(&class_name) {
+                        mro.push(class_name);
+                    }
+                }
+
+                break;
+            }
+        }
+    }
+
+    mro
+}
+
+fn index_classes(classes: &[Class]) -> HashMap<&str, &Class> {
+    let mut classes_by_name = HashMap::new();
+
+    for class in classes.iter() {
+        classes_by_name.entry(class.name.as_str()).or_insert(class);
+    }
+
+    classes_by_name
+}
+
+// enums and TypedDicts can extend another one defined in the module,
+// for example to mix required and optional keys
+fn resolve_kinds(classes: &mut [Class]) {
+    let classes_by_name = index_classes(classes);
+
+    let kinds = classes
+        .iter()
+        .map(|class| {
+            if class.kind != ClassKind::Class {
+                return class.kind;
+            }
+
+            linearize(&class.name, &classes_by_name, &mut Vec::new())
+                .iter()
+                .skip(1)
+                .map(|ancestor| classes_by_name[ancestor.as_str()].kind)
+                .find(|kind| matches!(kind, ClassKind::Enum | ClassKind::TypedDict))
+                .unwrap_or(ClassKind::Class)
+        })
+        .collect::<Vec<ClassKind>>();
+
+    for (class, kind) in classes.iter_mut().zip(kinds) {
+        class.set_kind(kind);
+    }
+}
+
+fn resolve_inherited_members(classes: &mut [Class]) {
+    let classes_by_name = index_classes(classes);
+
+    let mut inherited_members = Vec::new();
+
+    for class in classes.iter() {
+        let mro = linearize(&class.name, &classes_by_name, &mut Vec::new());
+
+        // members defined in the class itself override the inherited ones
+        let mut seen = class
+            .methods
+            .iter()
+            .map(|method| method.name.clone())
+            .chain(
+                class
+                    .attributes
+                    .iter()
+                    .map(|attribute| attribute.name.clone()),
+            )
+            .chain(class.members.iter().map(|member| member.name.clone()))
+            .collect::<Vec<String>>();
+
+        let mut members = Vec::new();
+
+        for ancestor in mro.iter().skip(1) {
+            let ancestor = classes_by_name[ancestor.as_str()];
+
+            let methods = ancestor
+                .methods
+                .iter()
+                .map(|method| (&method.name, MemberKind::Method, &method.docstring.title));
+            let attributes = ancestor.attributes.iter().map(|attribute| {
+                (
+                    &attribute.name,
+                    MemberKind::Attribute,
+                    &attribute.docstring.title,
+                )
+            });
+
+            for (name, kind, summary) in attributes.chain(methods) {
+                if seen.contains(name) {
+                    continue;
+                }
+
+                seen.push(name.clone());
+                members.push(InheritedMember {
+                    name: name.clone(),
+                    kind,
+                    defined_in: ancestor.name.clone(),
+                    summary: summary.clone(),
+                });
+            }
+        }
+
+        inherited_members.push(members);
+    }
+
+    for (class, members) in classes.iter_mut().zip(inherited_members) {
+        class.inherited_members = members;
+    }
+}
+
+// keeps track of the definitions found in the module, including the ones nested
+// in if/try/with blocks, so that we can skip definitions already found in another branch
+struct Definitions<'a> {
+    context: Context<'a>,
+    variables: Vec<Variable>,
+    functions: Vec<Function>,
+    classes: Vec<Class>,
+    branches: HashMap<String, usize>,
+    branch_count: usize,
+}
+
+// the module body itself is always the first branch
+const TOP_LEVEL_BRANCH: usize = 0;
+
+impl Definitions<'_> {
+    fn declare(&mut self, name: &str, branch: usize) -> bool {
+        match self.branches.get(name) {
+            Some(existing_branch) if *existing_branch == branch => true,
+            // blocks are visited where they appear, so a guarded definition can
+            // be found before the unguarded one, which is what the module exports
+            Some(_) if branch == TOP_LEVEL_BRANCH => {
+                self.variables.retain(|variable| variable.name != name);
+                self.functions.retain(|function| function.name != name);
+                self.classes.retain(|class| class.name != name);
+                self.branches.insert(name.to_string(), branch);
+                true
+            }
+            Some(_) => false,
+            None => {
+                self.branches.insert(name.to_string(), branch);
+                true
+            }
+        }
+    }
+}
+
+fn combine_guards(guard: &Option<String>, condition: String) -> Option<String> {
+    match guard {
+        Some(guard) => Some(format!("{} and {}", guard, condition)),
+        None => Some(condition),
+    }
+}
+
+fn extract_definitions(
+    body: Vec<Located<StmtKind>>,
+    guard: Option<String>,
+    definitions: &mut Definitions,
+) {
+    let context = definitions.context;
+
+    let branch = definitions.branch_count;
+    definitions.branch_count += 1;
+
+    for mut variable in extract_variables(&body, &context) {
+        if definitions.declare(&variable.name, branch) {
+            variable.guard = guard.clone();
+            definitions.variables.push(variable);
+        }
+    }
+
+    // find all functions (sync and async) and classes in ast
+    for statement in body {
+        let location = source_location(context.path, &statement);
+
+        match statement.node {
+            StmtKind::FunctionDef {
+                name,
+                body,
+                args,
+                decorator_list,
+                returns,
+                type_comment: _,
+            } => {
+                let mut function = extract_function(
+                    name,
+                    context.parse_docstring(&extract_docstring(&body)),
+                    args,
+                    returns,
+                    decorator_list,
+                    false,
+                    location,
+                );
+
+                if definitions.declare(&function.name, branch) {
+                    function.guard = guard.clone();
+                    definitions.functions.push(function);
+                }
+            }
+            StmtKind::AsyncFunctionDef {
+                name,
+                body,
+                args,
+                decorator_list,
+                returns,
+                type_comment: _,
+            } => {
+                let mut function = extract_function(
+                    name,
+                    context.parse_docstring(&extract_docstring(&body)),
+                    args,
+                    returns,
+                    decorator_list,
+                    true,
+                    location,
+                );
+
+                if definitions.declare(&function.name, branch) {
+                    function.guard = guard.clone();
+                    definitions.functions.push(function);
+                }
+            }
+            StmtKind::ClassDef {
+                name,
+                bases,
+                keywords,
+                body,
+                decorator_list,
+            } => {
+                let mut class = extract_class(
+                    name,
+                    body,
+                    bases,
+                    keywords,
+                    decorator_list,
+                    &context,
+                    location,
+                );
+
+                if definitions.declare(&class.name, branch) {
+                    class.guard = guard.clone();
+                    definitions.classes.push(class);
+                }
+            }
+            // definitions under `if TYPE_CHECKING:`, `if sys.version_info >= ...:`
+            // and similar are still part of the module
+            StmtKind::If { test, body, orelse } => {
+                let test = test.to_string();
+
+                extract_definitions(body, combine_guards(&guard, test.clone()), definitions);
+                extract_definitions(
+                    orelse,
+                    combine_guards(&guard, format!("not ({})", test)),
+                    definitions,
+                );
+            }
+            StmtKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+            } => {
+                extract_definitions(body, combine_guards(&guard, "try".to_string()), definitions);
+
+                for handler in handlers {
+                    let ExcepthandlerKind::ExceptHandler {
+                        type_,
+                        name: _,
+                        body,
+                    } = handler.node;
+
+                    let condition = match type_ {
+                        Some(type_) => format!("except {}", type_),
+                        None => "except".to_string(),
+                    };
+
+                    extract_definitions(body, combine_guards(&guard, condition), definitions);
+                }
+
+                extract_definitions(
+                    orelse,
+                    combine_guards(&guard, "try".to_string()),
+                    definitions,
+                );
+                // finally always runs, so it doesn't add a condition
+                extract_definitions(finalbody, guard.clone(), definitions);
+            }
+            StmtKind::With {
+                items,
+                body,
+                type_comment: _,
+            } => {
+                let items = items
+                    .iter()
+                    .map(|item| item.context_expr.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                extract_definitions(
+                    body,
+                    combine_guards(&guard, format!("with {}", items)),
+                    definitions,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Extracts the documentation of a module, `path` is used to record where each
+/// item is defined and the docstrings are parsed with the given parsers,
+/// `style` forces every docstring to be parsed with the parser of that name.
+pub fn extract_with_parsers(
+    code: &str,
+    path: &str,
+    parsers: &docstrings::DocstringParsers,
+    style: Option<&str>,
+) -> Module {
+    let python_ast = parser::parse_program(&code, path).expect("Unable to parse");
+
+    let context = Context {
+        path,
+        parsers,
+        style,
+    };
+
+    let docstring = context.parse_docstring(&extract_docstring(&python_ast));
+
+    let all = extract_all(&python_ast);
+
+    let mut definitions = Definitions {
+        context,
+        variables: Vec::new(),
+        functions: Vec::new(),
+        classes: Vec::new(),
+        branches: HashMap::new(),
+        branch_count: TOP_LEVEL_BRANCH,
+    };
+
+    extract_definitions(python_ast, None, &mut definitions);
+
+    resolve_kinds(&mut definitions.classes);
+    resolve_inherited_members(&mut definitions.classes);
+
+    Module {
+        docstring,
+        all,
+        variables: definitions.variables,
+        functions: group_overloads(definitions.functions),
+        classes: definitions.classes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::docstrings::DocstringParsers;
+    use crate::extract::{ArgumentKind, ClassKind, DecoratorKind, MemberKind, VariableKind};
+
+    fn extract(code: &str) -> super::Module {
+        super::extract_with_parsers(code, "", &DocstringParsers::default(), None)
+    }
+
+    #[test]
+    fn it_parses_python_function() {
+        let code = r#"
+        def foo(a: int, b: int) -> int:
+            """Example docstring
+
+            Args:
+                a: a number
+                b: another number
+                directives:
+                    a Sequence of schema directives, this will be outputted in the schema
+                    when printing it.
+
+
+            Returns:
+                the sum of a and b
+
+            Raises:
+                ValueError: if a or b are not numbers
+            """
+            return a + b
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.functions.len(), 1);
+
+        let function = &result.functions[0];
+
+        assert_eq!(function.name, "foo");
+        assert_eq!(function.docstring.title, "Example docstring");
+        assert_eq!(function.docstring.description, "");
+        assert_eq!(function.docstring.arguments.len(), 3);
+
+        let arguments = &function.docstring.arguments;
+
+        assert_eq!(arguments[0].name, "a");
         assert_eq!(arguments[0].description, Some("a number".to_string()));
         assert_eq!(arguments[1].name, "b");
-        assert_eq!(arguments[1].description, Some("another number".to_string()));
-        assert_eq!(arguments[2].name, "directives");
+        assert_eq!(arguments[1].description, Some("another number".to_string()));
+        assert_eq!(arguments[2].name, "directives");
+        assert_eq!(
+            arguments[2].description,
+            Some(
+                "a Sequence of schema directives, this will be outputted in the schema when printing it."
+                    .to_string()
+            )
+        );
+
+        assert_eq!(function.returns, Some("int".to_string()));
+        assert_eq!(function.signature, "def foo(a: int, b: int) -> int");
+        assert_eq!(function.docstring.returns, "the sum of a and b");
+        assert_eq!(function.docstring.raises.len(), 1);
+        assert_eq!(function.docstring.raises[0].exception, "ValueError");
+        assert_eq!(
+            function.docstring.raises[0].description,
+            Some("if a or b are not numbers".to_string())
+        );
+    }
+
+    #[test]
+    fn it_parses_python_class() {
+        let code = r#"
+        class Foo(Base, metaclass=Meta):
+            """A class
+
+            This class does things.
+            """
+
+            def bar(self, a: int) -> int:
+                """Example method
+
+                Args:
+                    a: a number
+                """
+                return a
+
+            def baz(self):
+                pass
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.functions.len(), 0);
+        assert_eq!(result.classes.len(), 1);
+
+        let class = &result.classes[0];
+
+        assert_eq!(class.name, "Foo");
+        assert_eq!(class.docstring.title, "A class");
+        assert_eq!(class.docstring.description, "This class does things.");
+        assert_eq!(class.bases, vec!["Base".to_string()]);
+        assert_eq!(class.methods.len(), 2);
+
+        assert_eq!(class.methods[0].name, "bar");
+        assert_eq!(class.methods[0].docstring.title, "Example method");
+        assert_eq!(class.methods[0].arguments.len(), 1);
+        assert_eq!(class.methods[0].arguments[0].name, "a");
+        assert_eq!(class.methods[0].arguments[0].type_, Some("int".to_string()));
+
+        assert_eq!(class.methods[1].name, "baz");
+        assert_eq!(class.methods[1].docstring.title, "");
+    }
+
+    #[test]
+    fn it_parses_module_docstring() {
+        let code = r#"
+        """Module title
+
+        Some overview of the module.
+        """
+
+        def foo(a: int) -> int:
+            """Example docstring"""
+            return a
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.docstring.title, "Module title");
+        assert_eq!(result.docstring.description, "Some overview of the module.");
+        assert_eq!(result.functions.len(), 1);
+        assert_eq!(result.functions[0].docstring.title, "Example docstring");
+    }
+
+    #[test]
+    fn it_parses_async_functions() {
+        let code = r#"
+        async def foo(a: int) -> int:
+            """Example coroutine"""
+            return a
+
+        def bar(a: int) -> int:
+            """Example function"""
+            return a
+
+        class Foo:
+            async def baz(self):
+                """Example async method"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.functions.len(), 2);
+
+        assert_eq!(result.functions[0].name, "foo");
+        assert!(result.functions[0].is_async);
+        assert_eq!(result.functions[0].docstring.title, "Example coroutine");
+
+        assert_eq!(result.functions[1].name, "bar");
+        assert!(!result.functions[1].is_async);
+
+        let methods = &result.classes[0].methods;
+
+        assert_eq!(methods.len(), 1);
+        assert_eq!(methods[0].name, "baz");
+        assert!(methods[0].is_async);
+    }
+
+    #[test]
+    fn it_parses_return_annotations() {
+        let code = r#"
+        def foo(cls: T) -> Union[T, Callable[[T], T]]:
+            """Example docstring"""
+
+        def bar(a: int):
+            """Example docstring"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(
+            result.functions[0].returns,
+            Some("Union[T, Callable[[T], T]]".to_string())
+        );
+        assert_eq!(result.functions[0].docstring.returns, "");
+        assert_eq!(result.functions[1].returns, None);
+    }
+
+    #[test]
+    fn it_parses_positional_defaults() {
+        let code = r#"
+        def foo(a: int, b: int = 3, *, c: str = "c") -> int:
+            """Example docstring
+
+            Args:
+                a: a number
+                b: another number
+                c: a string
+            """
+            return a + b
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let arguments = &result.functions[0].arguments;
+
+        assert_eq!(arguments.len(), 3);
+        assert_eq!(arguments[0].name, "a");
+        assert_eq!(arguments[0].default, None);
+        assert_eq!(arguments[1].name, "b");
+        assert_eq!(arguments[1].default, Some("3".to_string()));
+        assert_eq!(arguments[2].name, "c");
+        assert_eq!(arguments[2].default, Some("'c'".to_string()));
+    }
+
+    #[test]
+    fn it_keeps_unannotated_parameters() {
+        let code = r#"
+        def foo(a, b: int, *, c=None):
+            """Example docstring
+
+            Args:
+                a: a value
+                b: a number
+                c: another value
+            """
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let arguments = &result.functions[0].arguments;
+
+        assert_eq!(arguments.len(), 3);
+        assert_eq!(arguments[0].name, "a");
+        assert_eq!(arguments[0].type_, None);
+        assert_eq!(arguments[0].description, Some("a value".to_string()));
+        assert_eq!(arguments[1].name, "b");
+        assert_eq!(arguments[1].type_, Some("int".to_string()));
+        assert_eq!(arguments[2].name, "c");
+        assert_eq!(arguments[2].type_, None);
+        assert_eq!(arguments[2].default, Some("None".to_string()));
+    }
+
+    #[test]
+    fn it_parses_argument_kinds() {
+        let code = r#"
+        def foo(a, /, b, *args, c, **kwargs):
+            """Example docstring
+
+            Args:
+                a: positional only
+                b: positional or keyword
+                *args: extra positional arguments
+                c: keyword only
+                **kwargs: extra keyword arguments
+            """
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let arguments = &result.functions[0].arguments;
+
+        assert_eq!(arguments.len(), 5);
+
+        assert_eq!(arguments[0].name, "a");
+        assert_eq!(arguments[0].kind, ArgumentKind::PositionalOnly);
+        assert_eq!(arguments[1].name, "b");
+        assert_eq!(arguments[1].kind, ArgumentKind::PositionalOrKeyword);
+        assert_eq!(arguments[2].name, "args");
+        assert_eq!(arguments[2].kind, ArgumentKind::VarPositional);
+        assert_eq!(
+            arguments[2].description,
+            Some("extra positional arguments".to_string())
+        );
+        assert_eq!(arguments[3].name, "c");
+        assert_eq!(arguments[3].kind, ArgumentKind::KeywordOnly);
+        assert_eq!(arguments[4].name, "kwargs");
+        assert_eq!(arguments[4].kind, ArgumentKind::VarKeyword);
+    }
+
+    #[test]
+    fn it_parses_decorators() {
+        let code = r#"
+        class Foo:
+            @property
+            def bar(self) -> int:
+                """Example property"""
+
+            @staticmethod
+            @functools.cached_property
+            def baz() -> int:
+                """Example cached property"""
+
+            @deprecated("Use bar instead")
+            @some_decorator
+            def qux(self) -> int:
+                """Example deprecated method"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let methods = &result.classes[0].methods;
+
+        assert_eq!(methods[0].decorators, vec!["property".to_string()]);
+        assert_eq!(methods[0].decorator_kinds, vec![DecoratorKind::Property]);
+
+        assert_eq!(
+            methods[1].decorators,
+            vec![
+                "staticmethod".to_string(),
+                "functools.cached_property".to_string()
+            ]
+        );
+        assert_eq!(
+            methods[1].decorator_kinds,
+            vec![DecoratorKind::StaticMethod, DecoratorKind::CachedProperty]
+        );
+
+        assert_eq!(
+            methods[2].decorators,
+            vec![
+                "deprecated('Use bar instead')".to_string(),
+                "some_decorator".to_string()
+            ]
+        );
+        assert_eq!(methods[2].decorator_kinds, vec![DecoratorKind::Deprecated]);
+    }
+
+    #[test]
+    fn it_groups_overloads() {
+        let code = r#"
+        @overload
+        def field(resolver: None = None) -> Any:
+            ...
+
+        @typing.overload
+        def field(resolver: Callable) -> Any:
+            """Overload docstring
+
+            Args:
+                resolver: The resolver.
+            """
+
+        @deprecated("Use strawberry.field")
+        def field(resolver=None):
+            ...
+
+        @overload
+        def other(a: int) -> int:
+            ...
+
+        def bar(a: int) -> int:
+            """Example docstring"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.functions.len(), 3);
+
+        let field = &result.functions[0];
+
+        assert_eq!(field.name, "field");
+        assert_eq!(field.docstring.title, "Overload docstring");
+        assert_eq!(
+            field.overloads,
+            vec![
+                "def field(resolver: None = None) -> Any".to_string(),
+                "def field(resolver: Callable) -> Any".to_string()
+            ]
+        );
+        // everything else comes from the implementation
+        assert_eq!(field.signature, "def field(resolver=None)");
+        assert_eq!(field.arguments[0].annotation, None);
+        assert_eq!(
+            field.arguments[0].description,
+            Some("The resolver.".to_string())
+        );
+        assert!(field.arguments[0].documented);
+        assert_eq!(
+            field.decorators,
+            vec!["deprecated('Use strawberry.field')".to_string()]
+        );
+        assert_eq!(field.decorator_kinds, vec![DecoratorKind::Deprecated]);
+
+        assert_eq!(result.functions[1].name, "other");
+        assert_eq!(
+            result.functions[1].overloads,
+            vec!["def other(a: int) -> int".to_string()]
+        );
+
+        assert_eq!(result.functions[2].name, "bar");
+        assert_eq!(result.functions[2].overloads.len(), 0);
+    }
+
+    #[test]
+    fn it_parses_class_attributes() {
+        let code = r#"
+        class Foo:
+            """A class"""
+
+            field_abc: str = "ABC"
+            """The ABC field"""
+
+            field_without_default: int
+
+            counter = 0
+
+            def bar(self):
+                """A method"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let attributes = &result.classes[0].attributes;
+
+        assert_eq!(attributes.len(), 3);
+
+        assert_eq!(attributes[0].name, "field_abc");
+        assert_eq!(attributes[0].type_, Some("str".to_string()));
+        assert_eq!(attributes[0].default, Some("'ABC'".to_string()));
+        assert_eq!(attributes[0].docstring.title, "The ABC field");
+
+        assert_eq!(attributes[1].name, "field_without_default");
+        assert_eq!(attributes[1].type_, Some("int".to_string()));
+        assert_eq!(attributes[1].default, None);
+        assert_eq!(attributes[1].docstring.title, "");
+
+        assert_eq!(attributes[2].name, "counter");
+        assert_eq!(attributes[2].type_, None);
+        assert_eq!(attributes[2].default, Some("0".to_string()));
+
+        assert_eq!(result.classes[0].methods.len(), 1);
+    }
+
+    #[test]
+    fn it_parses_module_variables() {
+        let code = r#"
+        from typing import Final, TypeAlias, TypeVar, Union
+
+        T = TypeVar("T")
+
+        DEFAULT_TIMEOUT: int = 30
+        """The default timeout, in seconds."""
+
+        MAX_RETRIES: Final = 3
+
+        JSON = Union[dict, list, str]
+
+        Headers: TypeAlias = "dict[str, str]"
+
+        registry = {}
+
+        first = items[0]
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let variables = &result.variables;
+
+        assert_eq!(variables.len(), 7);
+
+        assert_eq!(variables[0].name, "T");
+        assert_eq!(variables[0].kind, VariableKind::TypeVar);
+        assert_eq!(variables[0].default, Some("TypeVar('T')".to_string()));
+
+        assert_eq!(variables[1].name, "DEFAULT_TIMEOUT");
+        assert_eq!(variables[1].kind, VariableKind::Constant);
+        assert_eq!(variables[1].type_, Some("int".to_string()));
+        assert_eq!(variables[1].default, Some("30".to_string()));
+        assert_eq!(
+            variables[1].docstring.title,
+            "The default timeout, in seconds."
+        );
+
+        assert_eq!(variables[2].name, "MAX_RETRIES");
+        assert_eq!(variables[2].kind, VariableKind::Constant);
+
+        assert_eq!(variables[3].name, "JSON");
+        assert_eq!(variables[3].kind, VariableKind::TypeAlias);
+
+        assert_eq!(variables[4].name, "Headers");
+        assert_eq!(variables[4].kind, VariableKind::TypeAlias);
+
+        assert_eq!(variables[5].name, "registry");
+        assert_eq!(variables[5].kind, VariableKind::Variable);
+
+        assert_eq!(variables[6].name, "first");
+        assert_eq!(variables[6].kind, VariableKind::Variable);
+    }
+
+    #[test]
+    fn it_parses_dunder_all() {
+        let code = r#"
+        __all__ = ["foo", "Bar"]
+        __all__ += ("_baz",)
+
+        def foo():
+            """Public function"""
+
+        def qux():
+            """Not exported"""
+
+        def _baz():
+            """Exported even if it starts with _"""
+
+        class Bar:
+            """Public class"""
+        "#;
+
+        let mut result = extract(&textwrap::dedent(code));
+
+        assert_eq!(
+            result.all,
+            Some(vec![
+                "foo".to_string(),
+                "Bar".to_string(),
+                "_baz".to_string()
+            ])
+        );
+        assert_eq!(result.variables.len(), 0);
+
+        result.retain_public();
+
+        let names = result
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(names, vec!["foo", "_baz"]);
+        assert_eq!(result.classes.len(), 1);
+    }
+
+    #[test]
+    fn it_treats_underscore_names_as_private_without_dunder_all() {
+        let code = r#"
+        _CACHE = {}
+
+        def foo():
+            """Public function"""
+
+        def _private_helper():
+            """Private function"""
+        "#;
+
+        let mut result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.all, None);
+
+        result.retain_public();
+
+        assert_eq!(result.variables.len(), 0);
+        assert_eq!(result.functions.len(), 1);
+        assert_eq!(result.functions[0].name, "foo");
+    }
+
+    #[test]
+    fn it_finds_definitions_in_conditional_blocks() {
+        let code = r#"
+        import sys
+        from typing import TYPE_CHECKING
+
+        if TYPE_CHECKING:
+            from typing import Literal
+
+            class Foo:
+                """Only for type checkers"""
+
+        if sys.version_info >= (3, 10):
+            def bar(a: int) -> int:
+                """New implementation"""
+        else:
+            def bar(a: int) -> int:
+                """Old implementation"""
+
+        try:
+            from fast import baz
+        except ImportError:
+            def baz() -> None:
+                """Fallback"""
+
+            FALLBACK = True
+
+        def qux() -> None:
+            """Top level"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].name, "Foo");
+        assert_eq!(result.classes[0].guard, Some("TYPE_CHECKING".to_string()));
+
+        assert_eq!(result.functions.len(), 3);
+
+        assert_eq!(result.functions[0].name, "bar");
+        assert_eq!(result.functions[0].docstring.title, "New implementation");
+        assert_eq!(
+            result.functions[0].guard,
+            Some("sys.version_info >= (3, 10)".to_string())
+        );
+
+        assert_eq!(result.functions[1].name, "baz");
+        assert_eq!(
+            result.functions[1].guard,
+            Some("except ImportError".to_string())
+        );
+
+        assert_eq!(result.functions[2].name, "qux");
+        assert_eq!(result.functions[2].guard, None);
+
+        assert_eq!(result.variables.len(), 1);
+        assert_eq!(result.variables[0].name, "FALLBACK");
+        assert_eq!(
+            result.variables[0].guard,
+            Some("except ImportError".to_string())
+        );
+    }
+
+    #[test]
+    fn it_prefers_unguarded_definitions() {
+        let code = r#"
+        from typing import TYPE_CHECKING
+
+        if TYPE_CHECKING:
+            def foo() -> None:
+                """Only for type checkers"""
+
+            class Bar:
+                """Only for type checkers"""
+
+        def foo() -> None:
+            """The real foo"""
+
+        class Bar:
+            """The real Bar"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.functions.len(), 1);
+        assert_eq!(result.functions[0].docstring.title, "The real foo");
+        assert_eq!(result.functions[0].guard, None);
+
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].docstring.title, "The real Bar");
+        assert_eq!(result.classes[0].guard, None);
+    }
+
+    #[test]
+    fn it_parses_enum_members() {
+        let code = r#"
+        class Color(enum.Enum):
+            """The colors"""
+
+            _ignore_ = ["DEBUG"]
+
+            RED = "red"
+            """The color of fire"""
+
+            GREEN = auto()
+
+            def describe(self) -> str:
+                """Describes the color"""
+
+        class Point:
+            x = 0
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let color = &result.classes[0];
+
+        assert_eq!(color.kind, ClassKind::Enum);
+        assert_eq!(color.members.len(), 2);
+
+        assert_eq!(color.members[0].name, "RED");
+        assert_eq!(color.members[0].value, "'red'");
+        assert_eq!(color.members[0].docstring.title, "The color of fire");
+
+        assert_eq!(color.members[1].name, "GREEN");
+        assert_eq!(color.members[1].value, "auto()");
+
+        assert_eq!(color.attributes.len(), 1);
+        assert_eq!(color.attributes[0].name, "_ignore_");
+        assert_eq!(color.methods.len(), 1);
+
+        let point = &result.classes[1];
+
+        assert_eq!(point.kind, ClassKind::Class);
+        assert_eq!(point.members.len(), 0);
+        assert_eq!(point.attributes.len(), 1);
+    }
+
+    #[test]
+    fn it_resolves_kinds_from_local_bases() {
+        let code = r#"
+        class MovieBase(TypedDict):
+            title: str
+
+        class Movie(MovieBase, total=False):
+            year: int
+            director: Required[str]
+
+        class BaseColor(enum.Enum):
+            def describe(self) -> str:
+                """Describes the color"""
+
+        class Color(BaseColor):
+            RED = 1
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let movie = &result.classes[1];
+
+        assert_eq!(movie.kind, ClassKind::TypedDict);
+        assert_eq!(movie.total, Some(false));
+        assert_eq!(movie.attributes[0].required, Some(false));
+        assert_eq!(movie.attributes[1].required, Some(true));
+
+        let color = &result.classes[3];
+
+        assert_eq!(color.kind, ClassKind::Enum);
+        assert_eq!(color.members.len(), 1);
+        assert_eq!(color.members[0].name, "RED");
+        assert_eq!(color.attributes.len(), 0);
+    }
+
+    #[test]
+    fn it_parses_typed_dicts_named_tuples_and_protocols() {
+        let code = r#"
+        class Movie(TypedDict, total=False):
+            title: Required[str]
+            year: int
+
+        class Book(typing.TypedDict):
+            title: str
+            isbn: NotRequired[str]
+
+        class Point(NamedTuple):
+            x: int
+            y: int = 0
+
+        class Greeter(Protocol[T]):
+            def greet(self, name: str) -> T:
+                """Greets someone"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let movie = &result.classes[0];
+
+        assert_eq!(movie.kind, ClassKind::TypedDict);
+        assert_eq!(movie.total, Some(false));
+        assert_eq!(movie.attributes[0].required, Some(true));
+        assert_eq!(movie.attributes[1].required, Some(false));
+
+        let book = &result.classes[1];
+
+        assert_eq!(book.kind, ClassKind::TypedDict);
+        assert_eq!(book.total, Some(true));
+        assert_eq!(book.attributes[0].required, Some(true));
+        assert_eq!(book.attributes[1].required, Some(false));
+
+        let point = &result.classes[2];
+
+        assert_eq!(point.kind, ClassKind::NamedTuple);
+        assert_eq!(point.total, None);
+        assert_eq!(point.attributes[0].name, "x");
+        assert_eq!(point.attributes[0].default, None);
+        assert_eq!(point.attributes[1].name, "y");
+        assert_eq!(point.attributes[1].default, Some("0".to_string()));
+
+        let greeter = &result.classes[3];
+
+        assert_eq!(greeter.kind, ClassKind::Protocol);
+        assert_eq!(greeter.methods.len(), 1);
+    }
+
+    #[test]
+    fn it_resolves_inherited_members() {
+        let code = r#"
+        class Base:
+            name: str
+            """The name"""
+
+            def greet(self) -> str:
+                """Greets"""
+
+            def describe(self) -> str:
+                """Describes"""
+
+        class Mixin:
+            def describe(self) -> str:
+                """Describes, from the mixin"""
+
+            def log(self) -> None:
+                """Logs"""
+
+        class Child(Mixin, Base, external.Thing):
+            def greet(self) -> str:
+                """Greets, overridden"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.classes[0].inherited_members.len(), 0);
+
+        let child = &result.classes[2];
+        let inherited = &child.inherited_members;
+
+        assert_eq!(inherited.len(), 3);
+
+        assert_eq!(inherited[0].name, "describe");
+        assert_eq!(inherited[0].kind, MemberKind::Method);
+        assert_eq!(inherited[0].defined_in, "Mixin");
+        assert_eq!(inherited[0].summary, "Describes, from the mixin");
+
+        assert_eq!(inherited[1].name, "log");
+        assert_eq!(inherited[1].defined_in, "Mixin");
+
+        assert_eq!(inherited[2].name, "name");
+        assert_eq!(inherited[2].kind, MemberKind::Attribute);
+        assert_eq!(inherited[2].defined_in, "Base");
+    }
+
+    #[test]
+    fn it_records_source_locations() {
+        let code = r#"
+        """Module docstring"""
+
+        VERSION = "1.0"
+
+        def foo(a: int) -> int:
+            """Example docstring"""
+            return a
+
+        class Bar:
+            field: str
+
+            def baz(self) -> None:
+                pass
+        "#;
+
+        let result = super::extract_with_parsers(
+            &textwrap::dedent(code),
+            "pkg/module.py",
+            &DocstringParsers::default(),
+            None,
+        );
+
+        assert_eq!(result.variables[0].location.path, "pkg/module.py");
+        assert_eq!(result.variables[0].location.start_line, 4);
+
+        let function = &result.functions[0];
+
+        assert_eq!(function.location.path, "pkg/module.py");
+        assert_eq!(function.location.start_line, 6);
+        assert_eq!(function.location.end_line, 8);
+
+        let class = &result.classes[0];
+
+        assert_eq!(class.location.start_line, 10);
+        assert_eq!(class.location.end_line, 14);
+        assert_eq!(class.attributes[0].location.start_line, 11);
+        assert_eq!(class.methods[0].location.start_line, 13);
+    }
+
+    #[test]
+    fn it_keeps_undocumented_arguments() {
+        let code = r#"
+        def foo(a: int, b: int) -> int:
+            """Example docstring
+
+            Args:
+                a: a number
+            """
+            return a + b
+
+        class Bar:
+            def baz(self, c: str) -> None:
+                """Example method"""
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let arguments = &result.functions[0].arguments;
+
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(arguments[0].name, "a");
+        assert!(arguments[0].documented);
+        assert_eq!(arguments[1].name, "b");
+        assert!(!arguments[1].documented);
+        assert_eq!(arguments[1].description, None);
+
+        // self is never reported as undocumented
+        let arguments = &result.classes[0].methods[0].arguments;
+
+        assert_eq!(arguments.len(), 1);
+        assert_eq!(arguments[0].name, "c");
+
+        let warnings = result
+            .warnings()
+            .iter()
+            .map(|warning| warning.message.clone())
+            .collect::<Vec<String>>();
+
         assert_eq!(
-            arguments[2].description,
-            Some(
-                "a Sequence of schema directives, this will be outputted in the schema when printing it."
-                    .to_string()
-            )
+            warnings,
+            vec![
+                "argument `b` of `foo` is not documented".to_string(),
+                "argument `c` of `Bar.baz` is not documented".to_string(),
+            ]
         );
+    }
 
-        assert_eq!(function.docstring.returns, "the sum of a and b");
-        assert_eq!(function.docstring.raises.len(), 1);
-        assert_eq!(function.docstring.raises[0].exception, "ValueError");
+    #[test]
+    fn it_uses_and_checks_documented_argument_types() {
+        let code = r#"
+        def foo(a, b: int, c: str = "c", d: Dict[str,int] = None, e=1):
+            """Example docstring
+
+            Args:
+                a (int): a number
+                b (str): another number
+                c (str, default: 'd'): a label
+                d (Dict[str, int], optional): a mapping
+                e (int, optional): the last one
+            """
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let arguments = &result.functions[0].arguments;
+
+        // the docstring type is used when there's no annotation,
+        // but only in the docs, the signature shows the code as written
+        assert_eq!(arguments[0].type_, Some("int".to_string()));
+        assert_eq!(arguments[0].annotation, None);
+        assert!(arguments[0].conflicts.is_empty());
+        assert!(result.functions[0]
+            .signature
+            .starts_with("def foo(a, b: int, "));
+
+        // but the annotation wins when they disagree
+        assert_eq!(arguments[1].type_, Some("int".to_string()));
         assert_eq!(
-            function.docstring.raises[0].description,
-            Some("if a or b are not numbers".to_string())
+            arguments[1].conflicts,
+            vec!["is annotated as `int` but documented as `str`".to_string()]
+        );
+
+        assert_eq!(
+            arguments[2].conflicts,
+            vec!["defaults to `'c'` but is documented with default `'d'`".to_string()]
+        );
+        assert!(arguments[3].conflicts.is_empty());
+        assert!(arguments[4].conflicts.is_empty());
+
+        let code = r#"
+        def bar(a: int, b: defaultdict):
+            """Example docstring
+
+            Args:
+                a (int, optional): a number
+                b (defaultdict): a factory
+            """
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        let warnings = result
+            .warnings()
+            .iter()
+            .map(|warning| warning.message.clone())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            warnings,
+            vec!["argument `a` of `bar` is documented as optional but has no default".to_string()]
+        );
+    }
+
+    #[test]
+    fn it_parses_generator_return_annotations() {
+        let code = r#"
+        def count() -> Generator[int, str, None]:
+            """Counts forever.
+
+            Yields:
+                The next number.
+
+            Receives:
+                A label for the next number.
+            """
+
+        async def stream() -> typing.AsyncGenerator[str, None]:
+            pass
+
+        def numbers() -> Iterator[int]:
+            pass
+
+        def total() -> int:
+            pass
+        "#;
+
+        let result = extract(&textwrap::dedent(code));
+
+        assert_eq!(result.functions[0].yields, Some("int".to_string()));
+        assert_eq!(result.functions[0].receives, Some("str".to_string()));
+        assert_eq!(result.functions[0].docstring.yields, "The next number.");
+        assert_eq!(
+            result.functions[0].docstring.receives,
+            "A label for the next number."
         );
+
+        assert_eq!(result.functions[1].yields, Some("str".to_string()));
+        assert_eq!(result.functions[1].receives, None);
+
+        assert_eq!(result.functions[2].yields, Some("int".to_string()));
+        assert_eq!(result.functions[2].receives, None);
+
+        assert_eq!(result.functions[3].yields, None);
     }
 }
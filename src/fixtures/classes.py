@@ -0,0 +1,21 @@
+class Greeter(Base):
+    """A class that greets people.
+
+    It keeps track of how many people it greeted.
+    """
+
+    greeting: str = "Hello"
+    """The greeting to use."""
+
+    count = 0
+
+    def greet(self, name: str) -> str:
+        """Greets someone.
+
+        Args:
+            name: The name of the person to greet.
+
+        Returns:
+            The greeting.
+        """
+        return f"Hello {name}"
@@ -0,0 +1,24 @@
+"""Utilities to work with numbers.
+
+This module contains a few helpers that are used
+across the whole codebase.
+
+>>> from numbers import add_one
+>>> add_one(1)
+"""
+
+from typing import TypeVar
+
+T = TypeVar("T")
+
+INCREMENT: int = 1
+"""How much `add_one` adds to its input."""
+
+
+def add_one(a: int) -> int:
+    """Adds one to the input value.
+
+    Args:
+        a: The input value.
+    """
+    return a + INCREMENT
@@ -1,3 +1,4 @@
+use crate::docstrings;
 use crate::extract;
 use std::{
     fs,
@@ -5,6 +6,33 @@ use std::{
 };
 
 use minijinja::{context, Environment};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct VariableSection<'a> {
+    title: &'static str,
+    variables: Vec<&'a extract::Variable>,
+}
+
+// each kind of variable gets its own section, empty sections are skipped
+fn variable_sections(variables: &[extract::Variable]) -> Vec<VariableSection> {
+    [
+        (extract::VariableKind::Constant, "Constants"),
+        (extract::VariableKind::Variable, "Variables"),
+        (extract::VariableKind::TypeAlias, "Type aliases"),
+        (extract::VariableKind::TypeVar, "Type variables"),
+    ]
+    .into_iter()
+    .map(|(kind, title)| VariableSection {
+        title,
+        variables: variables
+            .iter()
+            .filter(|variable| variable.kind == kind)
+            .collect(),
+    })
+    .filter(|section| !section.variables.is_empty())
+    .collect()
+}
 
 pub fn normalize_path(path: &Path) -> PathBuf {
     let mut components = path.components().peekable();
@@ -34,10 +62,52 @@ pub fn normalize_path(path: &Path) -> PathBuf {
 }
 
 // multline string
-const TEMPLATE: &str = r#"
-# {{ function_name }}
+const MODULE_TEMPLATE: &str = r#"
+{%- if module_docstring.title -%}
+{{ module_docstring.title }}
+{% for part in module_docstring.body -%}
+{%-if part.CodeSnippet -%}
+```python
+{{ part.CodeSnippet }}
+```
+{%-elif part.Text %}
+{{ part.Text }}
+{% endif %}
+{% endfor-%}
+{%- endif %}
+{%- for section in module_variable_sections %}
+## {{ section.title }}:
+
+| Name | Type | Value | Description |
+| --- | --- | --- | --- |
+{% for variable in section.variables -%}
+| {{ variable.name }} | {% if variable.type %}{{ variable.type }}{% else %}_untyped_{% endif %} | {% if variable.default %}`{{ variable.default }}`{% endif %} | {{ variable.docstring.title }} |
+{% endfor %}
+{%- endfor %}
+"#;
+
+const FUNCTION_TEMPLATE: &str = r#"
+{{ heading }} {% if function_is_async %}async {% endif %}{{ function_name }}{% if function_source %} [[source]]({{ function_source }}){% endif %}{% for kind in function_decorator_kinds %} `{{ kind }}`{% endfor %}{% if is_protocol_member %} `protocol`{% endif %}
 
-{{ function_docstring.title }}
+```python
+{% if function_overloads -%}
+{% for overload in function_overloads -%}
+@overload
+{{ overload }}
+{% if not loop.last %}
+{% endif -%}
+{% endfor -%}
+{% else -%}
+{% for decorator in function_decorators -%}
+@{{ decorator }}
+{% endfor -%}
+{{ signature }}
+{% endif -%}
+```
+
+{% if function_guard %}_Only defined under `{{ function_guard }}`._
+
+{% endif %}{{ function_docstring.title }}
 {% for part in function_docstring.body -%}
 {%-if part.CodeSnippet -%}
 ```python
@@ -48,32 +118,219 @@ const TEMPLATE: &str = r#"
 {% endif %}
 {% endfor-%}
 
-{% if function_docstring.arguments %}
-## Arguments:
+{% if function_arguments %}
+{{ heading }}# Arguments:
 
 
 | Name | Type | Description |
 | --- | --- | --- |
 {% for argument in function_arguments-%}
-| {{ argument.name }} | {{ argument.type }} | {{ argument.description }} |
+| {% if argument.kind == "var_positional" %}*{% elif argument.kind == "var_keyword" %}**{% endif %}{{ argument.name }}{% if argument.kind == "positional_only" %} _(positional-only)_{% elif argument.kind == "keyword_only" %} _(keyword-only)_{% endif %} | {% if argument.type %}{{ argument.type }}{% else %}_untyped_{% endif %} | {% if not argument.documented %}_undocumented_{% elif argument.description %}{{ argument.description }}{% endif %} |
 {% endfor %}
 {% endif %}
 
+{% if function_docstring.returns or function_docstring.returns_type or (function_returns and function_returns != "None") %}
+{{ heading }}# Returns:
+{% if function_returns %}
+`{{ function_returns }}`
+{% elif function_docstring.returns_type %}
+`{{ function_docstring.returns_type }}`
+{% endif %}
 {% if function_docstring.returns %}
-## Returns:
-
 {{ function_docstring.returns }}
 {% endif %}
+{% endif %}{% if function_docstring.yields or function_yields or function_docstring.yields_type %}
+{{ heading }}# Yields:
+{% if function_yields %}
+`{{ function_yields }}`
+{% elif function_docstring.yields_type %}
+`{{ function_docstring.yields_type }}`
+{% endif %}
+{% if function_docstring.yields %}
+{{ function_docstring.yields }}
+{% endif %}
+{% endif %}{% if function_docstring.receives or function_receives or function_docstring.receives_type %}
+{{ heading }}# Receives:
+{% if function_receives %}
+`{{ function_receives }}`
+{% elif function_docstring.receives_type %}
+`{{ function_docstring.receives_type }}`
+{% endif %}
+{% if function_docstring.receives %}
+{{ function_docstring.receives }}
+{% endif %}
+{% endif %}
 
 {% if function_docstring.raises %}
-## Raises:
+{{ heading }}# Raises:
 
 {{ function_docstring.raises }}
+{% endif %}{% if function_docstring.see_also %}
+{{ heading }}# See also:
+
+{{ function_docstring.see_also }}
+{% endif %}{% if function_docstring.notes %}
+{{ heading }}# Notes:
+
+{{ function_docstring.notes }}
 {% endif %}
 "#;
 
+const CLASS_TEMPLATE: &str = r#"
+# {{ class_name }}{% if class_source %} [[source]]({{ class_source }}){% endif %}
+{% if class_bases %}
+Bases: {{ class_bases|join(", ") }}
+{% endif %}
+{% if class_guard %}_Only defined under `{{ class_guard }}`._
+
+{% endif %}{% if class_kind == "protocol" %}_This is a protocol, its methods describe the structural interface that implementations must provide._
+
+{% endif %}{{ class_docstring.title }}
+{% for part in class_docstring.body -%}
+{%-if part.CodeSnippet -%}
+```python
+{{ part.CodeSnippet }}
+```
+{%-elif part.Text %}
+{{ part.Text }}
+{% endif %}
+{% endfor-%}
+
+{% if class_graphql %}
+## GraphQL {{ class_graphql.kind }} `{{ class_graphql.name }}`:
+{% if class_graphql.description %}
+{{ class_graphql.description }}
+{% endif %}
+| Field | Python name | Type | Description | Deprecated |
+| --- | --- | --- | --- | --- |
+{% for field in class_graphql.fields -%}
+| {{ field.name }}{% if field.is_resolver %} _(resolver)_{% endif %} | {{ field.python_name }} | {% if field.type %}{{ field.type }}{% else %}_untyped_{% endif %} | {% if field.description %}{{ field.description }}{% endif %} | {% if field.deprecation_reason %}{{ field.deprecation_reason }}{% endif %} |
+{% endfor %}
+{% endif %}{% if class_members %}
+## Members:
+
+| Name | Value | Description |
+| --- | --- | --- |
+{% for member in class_members -%}
+| {{ member.name }} | `{{ member.value }}` | {{ member.docstring.title }} |
+{% endfor %}
+{% endif %}{% if class_attributes and class_kind == "typed_dict" %}
+## Keys:
+{% if not class_total %}
+Declared with `total=False`, so keys are optional unless marked as `Required`.
+{% endif %}
+| Name | Type | Required | Description |
+| --- | --- | --- | --- |
+{% for attribute in class_attributes -%}
+| {{ attribute.name }} | {{ attribute.type }} | {% if attribute.required %}yes{% else %}no{% endif %} | {{ attribute.docstring.title }} |
+{% endfor %}
+{% elif class_attributes %}
+## {% if class_kind == "named_tuple" %}Fields{% else %}Attributes{% endif %}:
+
+| Name | Type | Default | Description |
+| --- | --- | --- | --- |
+{% for attribute in class_attributes -%}
+| {{ attribute.name }} | {% if attribute.type %}{{ attribute.type }}{% else %}_untyped_{% endif %} | {% if attribute.default %}`{{ attribute.default }}`{% endif %} | {{ attribute.docstring.title }} |
+{% endfor %}
+{% endif %}{% if class_inherited_members %}
+## Inherited members:
+
+| Name | Kind | Inherited from | Description |
+| --- | --- | --- | --- |
+{% for member in class_inherited_members -%}
+| {{ member.name }} | {{ member.kind }} | `{{ member.defined_in }}` | {{ member.summary }} |
+{% endfor %}
+{% endif %}
+"#;
+
+#[derive(Default)]
+pub struct Options {
+    /// Also document the items that are not part of the public API,
+    /// either because they are missing from `__all__` or start with `_`.
+    pub include_private: bool,
+    /// The path of the file being documented, relative to the repository root.
+    pub path: Option<String>,
+    /// A template for the "view source" links, for example
+    /// `https://github.com/org/repo/blob/{rev}/{path}#L{start}-L{end}`,
+    /// the links are only added when `path` is set.
+    pub source_url: Option<String>,
+    /// The revision used for `{rev}` in `source_url`, required when it's used.
+    pub rev: Option<String>,
+    /// Parse every docstring with this style, for example `numpy`,
+    /// instead of detecting the style of each docstring.
+    pub docstring_style: Option<String>,
+    /// The docstring parsers, custom ones can be added with `register`.
+    pub docstring_parsers: docstrings::DocstringParsers,
+}
+
+fn source_link(options: &Options, location: &extract::SourceLocation) -> Option<String> {
+    let source_url = options.source_url.as_ref()?;
+
+    // without a path the link would point to the root of the repository
+    options.path.as_ref()?;
+
+    let mut link = source_url
+        .replace("{path}", &location.path)
+        .replace("{start}", &location.start_line.to_string())
+        .replace("{end}", &location.end_line.to_string());
+
+    // there's no revision we could guess, like `main` or `master`
+    if link.contains("{rev}") {
+        link = link.replace("{rev}", options.rev.as_deref()?);
+    }
+
+    Some(link)
+}
+
 pub fn generate(code: &str) -> String {
-    let module = extract::extract(code);
+    generate_with_options(code, &Options::default())
+}
+
+fn extract_module(code: &str, options: &Options) -> extract::Module {
+    let mut module = extract::extract_with_parsers(
+        code,
+        options.path.as_deref().unwrap_or(""),
+        &options.docstring_parsers,
+        options.docstring_style.as_deref(),
+    );
+
+    if !options.include_private {
+        module.retain_public();
+    }
+
+    module
+}
+
+/// Returns the problems found while documenting the code, like arguments
+/// missing from the docstrings, for the items that `generate_with_options` renders.
+pub fn warnings(code: &str, options: &Options) -> Vec<extract::Warning> {
+    let mut warnings = Vec::new();
+
+    // an unknown style is most likely a typo, the docstrings
+    // are parsed as if no style was forced
+    if let Some(style) = &options.docstring_style {
+        if !options.docstring_parsers.has_style(style) {
+            warnings.push(extract::Warning {
+                message: format!(
+                    "unknown docstring style `{}`, detecting the style of each docstring instead",
+                    style
+                ),
+                location: extract::SourceLocation {
+                    path: options.path.clone().unwrap_or_default(),
+                    start_line: 1,
+                    end_line: 1,
+                },
+            });
+        }
+    }
+
+    warnings.extend(extract_module(code, options).warnings());
+
+    warnings
+}
+
+pub fn generate_with_options(code: &str, options: &Options) -> String {
+    let module = extract_module(code, options);
 
     // let path = Path::new("./templates/function.md");
 
@@ -85,20 +342,42 @@ pub fn generate(code: &str) -> String {
 
     // println!("normalised path is: {:?}", normalize_path(path));
 
-    let function_template = TEMPLATE; // fs::read_to_string(path).expect("Unable to read function template");
+    let function_template = FUNCTION_TEMPLATE; // fs::read_to_string(path).expect("Unable to read function template");
 
     let mut env = Environment::new();
+    env.add_template("module", MODULE_TEMPLATE).unwrap();
     env.add_template("function", &function_template).unwrap();
+    env.add_template("class", CLASS_TEMPLATE).unwrap();
 
     let function_template = env.get_template("function").unwrap();
+    let class_template = env.get_template("class").unwrap();
 
-    let mut output = String::new();
+    // the module docstring is the introduction of the page
+    let mut output = env
+        .get_template("module")
+        .unwrap()
+        .render(context!(
+            module_docstring => module.docstring,
+            module_variable_sections => variable_sections(&module.variables)
+        ))
+        .unwrap();
 
     for function in module.functions {
         let content = function_template
             .render(context!(
+                heading => "#",
                 function_name => function.name,
+                function_is_async => function.is_async,
                 function_docstring => function.docstring,
+                function_returns => function.returns,
+                function_yields => function.yields,
+                function_receives => function.receives,
+                signature => function.signature,
+                function_decorators => function.decorators,
+                function_decorator_kinds => function.decorator_kinds,
+                function_overloads => function.overloads,
+                function_guard => function.guard,
+                function_source => source_link(options, &function.location),
                 function_arguments => function.arguments
             ))
             .unwrap();
@@ -106,6 +385,52 @@ pub fn generate(code: &str) -> String {
         output.push_str(&content);
     }
 
+    for class in module.classes {
+        let is_protocol = class.kind == extract::ClassKind::Protocol;
+
+        let content = class_template
+            .render(context!(
+                class_name => class.name,
+                class_kind => class.kind,
+                class_total => class.total,
+                class_docstring => class.docstring,
+                class_guard => class.guard,
+                class_source => source_link(options, &class.location),
+                class_bases => class.bases,
+                class_members => class.members,
+                class_attributes => class.attributes,
+                class_inherited_members => class.inherited_members,
+                class_graphql => class.graphql
+            ))
+            .unwrap();
+
+        output.push_str(&content);
+
+        // methods are nested one level below their class
+        for method in class.methods {
+            let content = function_template
+                .render(context!(
+                    heading => "##",
+                    is_protocol_member => is_protocol,
+                    function_name => method.name,
+                    function_is_async => method.is_async,
+                    function_docstring => method.docstring,
+                    function_returns => method.returns,
+                    function_yields => method.yields,
+                    function_receives => method.receives,
+                    signature => method.signature,
+                    function_decorators => method.decorators,
+                    function_decorator_kinds => method.decorator_kinds,
+                    function_overloads => method.overloads,
+                    function_source => source_link(options, &method.location),
+                    function_arguments => method.arguments
+                ))
+                .unwrap();
+
+            output.push_str(&content);
+        }
+    }
+
     output
 }
 
@@ -119,4 +444,251 @@ mod tests {
 
         insta::assert_debug_snapshot!(super::generate(&code));
     }
+
+    #[test]
+    fn test_file_with_module_docstring() {
+        let code = fs::read_to_string("./src/fixtures/module.py").expect("Unable to read file");
+
+        insta::assert_debug_snapshot!(super::generate(&code));
+    }
+
+    #[test]
+    fn test_file_with_private_items() {
+        let code = r#"
+def public() -> None:
+    """A public function."""
+
+
+def _private() -> None:
+    """A private function."""
+
+
+class Public:
+    _cache: dict = {}
+
+    def __init__(self) -> None:
+        """Creates the instance."""
+
+    def _helper(self) -> None:
+        """A private helper."""
+"#;
+
+        let output = super::generate(code);
+
+        assert!(output.contains("# public"));
+        assert!(!output.contains("# _private"));
+        assert!(output.contains("## __init__"));
+        assert!(!output.contains("## _helper"));
+        assert!(!output.contains("_cache"));
+
+        let options = super::Options {
+            include_private: true,
+            ..Default::default()
+        };
+        let output = super::generate_with_options(code, &options);
+
+        assert!(output.contains("# public"));
+        assert!(output.contains("# _private"));
+        assert!(output.contains("## _helper"));
+        assert!(output.contains("| _cache |"));
+    }
+
+    #[test]
+    fn test_source_links() {
+        let code = r#"
+def foo() -> None:
+    """A function."""
+"#;
+
+        let options = super::Options {
+            path: Some("src/module.py".to_string()),
+            source_url: Some(
+                "https://github.com/org/repo/blob/{rev}/{path}#L{start}-L{end}".to_string(),
+            ),
+            rev: Some("v1.0".to_string()),
+            ..Default::default()
+        };
+        let output = super::generate_with_options(code, &options);
+
+        assert!(output.contains(
+            "# foo [[source]](https://github.com/org/repo/blob/v1.0/src/module.py#L2-L3)"
+        ));
+
+        let without_path = super::Options {
+            path: None,
+            source_url: options.source_url.clone(),
+            rev: options.rev.clone(),
+            ..Default::default()
+        };
+        let output = super::generate_with_options(code, &without_path);
+
+        assert!(!output.contains("[[source]]"));
+
+        let without_rev = super::Options {
+            path: options.path.clone(),
+            source_url: options.source_url.clone(),
+            rev: None,
+            ..Default::default()
+        };
+        let output = super::generate_with_options(code, &without_rev);
+
+        assert!(!output.contains("[[source]]"));
+    }
+
+    #[test]
+    fn test_strawberry_types() {
+        let code = r#"
+@strawberry.type(description="A user of the system")
+class User:
+    first_name: str
+    nickname: str = strawberry.field(deprecation_reason="Use first_name")
+"#;
+
+        let output = super::generate(code);
+
+        assert!(output.contains("## GraphQL type `User`:"));
+        assert!(output.contains("A user of the system"));
+        assert!(output.contains("| firstName | first_name | str |  |  |"));
+        assert!(output.contains("| nickname | nickname | str |  | Use first_name |"));
+    }
+
+    #[test]
+    fn test_undocumented_arguments() {
+        let code = r#"
+def foo(a: int, b: str) -> None:
+    """A function.
+
+    Args:
+        a: A number.
+    """
+"#;
+
+        let output = super::generate(code);
+
+        assert!(output.contains("| a | int | A number. |"));
+        assert!(output.contains("| b | str | _undocumented_ |"));
+
+        let warnings = super::warnings(code, &super::Options::default())
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            warnings,
+            vec!["line 2: argument `b` of `foo` is not documented".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_docstring_styles() {
+        let code = r#"
+def foo(a: int) -> int:
+    """A function.
+
+    :param a: A number.
+    """
+"#;
+
+        let output = super::generate(code);
+
+        assert!(output.contains("| a | int | A number. |"));
+
+        // forcing the google style leaves the field list in the description
+        let options = super::Options {
+            docstring_style: Some("google".to_string()),
+            ..Default::default()
+        };
+        let output = super::generate_with_options(code, &options);
+
+        assert!(output.contains(":param a: A number."));
+        assert!(output.contains("| a | int | _undocumented_ |"));
+
+        // a typo in the style is reported, and the style is detected instead
+        let options = super::Options {
+            docstring_style: Some("numpi".to_string()),
+            ..Default::default()
+        };
+        let output = super::generate_with_options(code, &options);
+
+        assert!(output.contains("| a | int | A number. |"));
+
+        let warnings = super::warnings(code, &options)
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            warnings,
+            vec![
+                "line 1: unknown docstring style `numpi`, detecting the style of each docstring instead"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generators() {
+        let code = r#"
+def count() -> Generator[int, None, None]:
+    """Counts forever.
+
+    Yields:
+        The next number.
+    """
+"#;
+
+        let output = super::generate(code);
+
+        assert!(output.contains("## Yields:\n\n`int`\n"));
+        assert!(output.contains("The next number."));
+        assert!(!output.contains("## Receives:"));
+    }
+
+    #[test]
+    fn test_functions_returning_none() {
+        let code = r#"
+def reset() -> None:
+    """Resets the counter."""
+
+
+def clear() -> None:
+    """Clears the cache.
+
+    Returns:
+        Nothing, the cache is cleared in place.
+    """
+"#;
+
+        let output = super::generate(code);
+
+        // the annotation alone doesn't say anything worth a section
+        assert_eq!(output.matches("## Returns:").count(), 1);
+        assert!(output.contains("Nothing, the cache is cleared in place."));
+    }
+
+    #[test]
+    fn test_documented_return_types() {
+        let code = r#"
+def fetch(keys):
+    """Fetches rows.
+
+    :param keys: The keys.
+    :returns: The rows.
+    :rtype: dict
+    """
+"#;
+
+        let output = super::generate(code);
+
+        assert!(output.contains("## Returns:\n\n`dict`\n"));
+        assert!(output.contains("The rows."));
+        assert!(!output.contains("dict: The rows."));
+    }
+
+    #[test]
+    fn test_file_with_classes() {
+        let code = fs::read_to_string("./src/fixtures/classes.py").expect("Unable to read file");
+
+        insta::assert_debug_snapshot!(super::generate(&code));
+    }
 }
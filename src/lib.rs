@@ -1,11 +1,63 @@
 mod cleandoc;
-mod docstrings;
+pub mod docstrings;
 mod extract;
 mod generate;
+mod signature;
+mod strawberry;
 
+pub use generate::{generate_with_options, warnings, Options};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub fn get_markdown(code: &str) -> String {
     generate::generate(&code)
 }
+
+// the options shared by the markdown and the warnings
+fn options(
+    include_private: bool,
+    path: Option<String>,
+    docstring_style: Option<String>,
+) -> generate::Options {
+    generate::Options {
+        include_private,
+        path,
+        docstring_style,
+        ..Default::default()
+    }
+}
+
+#[wasm_bindgen]
+pub fn get_markdown_with_options(
+    code: &str,
+    include_private: bool,
+    path: Option<String>,
+    source_url: Option<String>,
+    rev: Option<String>,
+    docstring_style: Option<String>,
+) -> String {
+    let options = generate::Options {
+        source_url,
+        rev,
+        ..options(include_private, path, docstring_style)
+    };
+
+    generate::generate_with_options(code, &options)
+}
+
+/// Returns the warnings found while documenting the code, one string per warning,
+/// the options are the ones of `get_markdown_with_options` that affect them.
+#[wasm_bindgen]
+pub fn get_warnings(
+    code: &str,
+    include_private: bool,
+    path: Option<String>,
+    docstring_style: Option<String>,
+) -> Box<[JsValue]> {
+    let options = options(include_private, path, docstring_style);
+
+    generate::warnings(code, &options)
+        .iter()
+        .map(|warning| JsValue::from_str(&warning.to_string()))
+        .collect()
+}
@@ -1,13 +1,16 @@
 use std::fs;
 
-mod cleandoc;
-mod docstrings;
-mod extract;
-mod generate;
+use rusty_docs::{generate_with_options, warnings, Options};
 
 fn main() {
     let code =
         fs::read_to_string("./src/fixtures/strawberry_type.py").expect("Unable to read file");
 
-    println!("{}", generate::generate(&code));
+    let options = Options::default();
+
+    for warning in warnings(&code, &options) {
+        eprintln!("warning: {}", warning);
+    }
+
+    println!("{}", generate_with_options(&code, &options));
 }
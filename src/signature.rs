@@ -0,0 +1,208 @@
+use crate::extract::{Argument, ArgumentKind};
+
+// same as black's default line length
+const MAX_LINE_LENGTH: usize = 88;
+
+fn format_argument(argument: &Argument) -> String {
+    let prefix = match argument.kind {
+        ArgumentKind::VarPositional => "*",
+        ArgumentKind::VarKeyword => "**",
+        _ => "",
+    };
+
+    // PEP 8 wants spaces around = only when the argument is annotated
+    match (&argument.annotation, &argument.default) {
+        (Some(type_), Some(default)) => {
+            format!("{}{}: {} = {}", prefix, argument.name, type_, default)
+        }
+        (Some(type_), None) => format!("{}{}: {}", prefix, argument.name, type_),
+        (None, Some(default)) => format!("{}{}={}", prefix, argument.name, default),
+        (None, None) => format!("{}{}", prefix, argument.name),
+    }
+}
+
+fn format_parameters(arguments: &[Argument]) -> Vec<String> {
+    let mut parameters = Vec::new();
+
+    let has_var_positional = arguments
+        .iter()
+        .any(|argument| argument.kind == ArgumentKind::VarPositional);
+
+    for (index, argument) in arguments.iter().enumerate() {
+        // keyword only arguments need a bare * before them, unless there's already *args
+        if argument.kind == ArgumentKind::KeywordOnly
+            && !has_var_positional
+            && (index == 0 || arguments[index - 1].kind != ArgumentKind::KeywordOnly)
+        {
+            parameters.push("*".to_string());
+        }
+
+        parameters.push(format_argument(argument));
+
+        // positional only arguments are followed by a /
+        if argument.kind == ArgumentKind::PositionalOnly
+            && !matches!(
+                arguments.get(index + 1),
+                Some(next) if next.kind == ArgumentKind::PositionalOnly
+            )
+        {
+            parameters.push("/".to_string());
+        }
+    }
+
+    parameters
+}
+
+/// Formats a function signature the way black would, putting the
+/// parameters on their own lines when they don't fit on a single one.
+pub fn format_signature(
+    name: &str,
+    arguments: &[Argument],
+    returns: Option<&str>,
+    is_async: bool,
+) -> String {
+    let def = if is_async { "async def" } else { "def" };
+
+    let returns = match returns {
+        Some(returns) => format!(" -> {}", returns),
+        None => "".to_string(),
+    };
+
+    let parameters = format_parameters(arguments);
+
+    let signature = format!("{} {}({}){}", def, name, parameters.join(", "), returns);
+
+    // black measures the line with the trailing colon of the definition,
+    // so the signature itself has to be shorter than the limit
+    if signature.chars().count() < MAX_LINE_LENGTH || parameters.is_empty() {
+        return signature;
+    }
+
+    let parameters = parameters
+        .iter()
+        .map(|parameter| format!("    {},\n", parameter))
+        .collect::<String>();
+
+    format!("{} {}(\n{}){}", def, name, parameters, returns)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::extract::{Argument, ArgumentKind};
+
+    fn argument(
+        name: &str,
+        kind: ArgumentKind,
+        type_: Option<&str>,
+        default: Option<&str>,
+    ) -> Argument {
+        Argument {
+            name: name.to_string(),
+            kind,
+            annotation: type_.map(|type_| type_.to_string()),
+            type_: type_.map(|type_| type_.to_string()),
+            default: default.map(|default| default.to_string()),
+            description: None,
+            documented: true,
+            conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn it_formats_short_signatures_on_one_line() {
+        let arguments = vec![
+            argument("a", ArgumentKind::PositionalOnly, Some("int"), None),
+            argument("b", ArgumentKind::PositionalOrKeyword, None, Some("3")),
+            argument("args", ArgumentKind::VarPositional, None, None),
+            argument("c", ArgumentKind::KeywordOnly, Some("str"), Some("'c'")),
+            argument("kwargs", ArgumentKind::VarKeyword, Some("Any"), None),
+        ];
+
+        assert_eq!(
+            super::format_signature("foo", &arguments, Some("int"), false),
+            "def foo(a: int, /, b=3, *args, c: str = 'c', **kwargs: Any) -> int"
+        );
+    }
+
+    #[test]
+    fn it_adds_a_bare_star_before_keyword_only_arguments() {
+        let arguments = vec![
+            argument("a", ArgumentKind::PositionalOrKeyword, None, None),
+            argument("b", ArgumentKind::KeywordOnly, None, None),
+            argument("c", ArgumentKind::KeywordOnly, None, None),
+        ];
+
+        assert_eq!(
+            super::format_signature("foo", &arguments, None, true),
+            "async def foo(a, *, b, c)"
+        );
+    }
+
+    #[test]
+    fn it_wraps_long_signatures() {
+        let arguments = vec![
+            argument(
+                "cls",
+                ArgumentKind::PositionalOrKeyword,
+                Some("Optional[T]"),
+                None,
+            ),
+            argument(
+                "name",
+                ArgumentKind::KeywordOnly,
+                Some("Optional[str]"),
+                None,
+            ),
+            argument(
+                "is_input",
+                ArgumentKind::KeywordOnly,
+                Some("bool"),
+                Some("False"),
+            ),
+            argument(
+                "description",
+                ArgumentKind::KeywordOnly,
+                Some("Optional[str]"),
+                Some("None"),
+            ),
+        ];
+
+        assert_eq!(
+            super::format_signature("type", &arguments, Some("Union[T, Callable[[T], T]]"), false),
+            "def type(\n    cls: Optional[T],\n    *,\n    name: Optional[str],\n    is_input: bool = False,\n    description: Optional[str] = None,\n) -> Union[T, Callable[[T], T]]"
+        );
+    }
+
+    #[test]
+    fn it_counts_the_trailing_colon_in_the_line_length() {
+        // `def foo(...):` is exactly 88 characters long
+        let name = "x".repeat(78);
+        let arguments = vec![argument(
+            &name,
+            ArgumentKind::PositionalOrKeyword,
+            None,
+            None,
+        )];
+
+        assert_eq!(
+            super::format_signature("foo", &arguments, None, false),
+            format!("def foo({})", name)
+        );
+
+        // one more character and the colon goes over the limit
+        let name = "x".repeat(79);
+        let arguments = vec![argument(
+            &name,
+            ArgumentKind::PositionalOrKeyword,
+            None,
+            None,
+        )];
+
+        assert_eq!(
+            super::format_signature("foo", &arguments, None, false),
+            format!("def foo(\n    {},\n)", name)
+        );
+    }
+}
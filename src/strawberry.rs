@@ -0,0 +1,312 @@
+use rustpython_ast::{Constant, Expr, ExprKind, Keyword, Located, StmtKind};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphQLKind {
+    Type,
+    Input,
+    Interface,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GraphQLField {
+    /// The name of the attribute or resolver in Python
+    pub python_name: String,
+    /// The name of the field in the GraphQL schema
+    pub name: String,
+    #[serde(rename(serialize = "type"))]
+    pub type_: Option<String>,
+    pub description: Option<String>,
+    pub deprecation_reason: Option<String>,
+    pub is_resolver: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GraphQLType {
+    pub kind: GraphQLKind,
+    pub name: String,
+    pub description: Option<String>,
+    pub fields: Vec<GraphQLField>,
+}
+
+/// Converts a Python name to the name Strawberry uses in the schema,
+/// this follows `strawberry.utils.str_converters.to_camel_case`.
+pub fn to_camel_case(name: &str) -> String {
+    let mut components = name.split('_');
+
+    let mut camel_case = components.next().unwrap_or("").to_string();
+
+    for component in components {
+        let mut chars = component.chars();
+
+        match chars.next() {
+            Some(first) => {
+                camel_case.extend(first.to_uppercase());
+                camel_case.push_str(&chars.as_str().to_lowercase());
+            }
+            None => camel_case.push('_'),
+        }
+    }
+
+    camel_case
+}
+
+// returns the name of the decorator (or called function) without its arguments,
+// for example `strawberry.type(name="X")` becomes `strawberry.type`
+fn callee_name(expr: &Expr) -> String {
+    match &expr.node {
+        ExprKind::Call {
+            func,
+            args: _,
+            keywords: _,
+        } => func.to_string(),
+        _ => expr.to_string(),
+    }
+}
+
+fn call_keywords(expr: &Expr) -> &[Keyword] {
+    match &expr.node {
+        ExprKind::Call {
+            func: _,
+            args: _,
+            keywords,
+        } => keywords,
+        _ => &[],
+    }
+}
+
+// only string literals are supported, anything else can't be known statically
+fn keyword_string(keywords: &[Keyword], name: &str) -> Option<String> {
+    keywords
+        .iter()
+        .find(|keyword| keyword.node.arg.as_deref() == Some(name))
+        .and_then(|keyword| match &keyword.node.value.node {
+            ExprKind::Constant {
+                value: Constant::Str(value),
+                kind: _,
+            } => Some(value.clone()),
+            _ => None,
+        })
+}
+
+fn has_keyword(keywords: &[Keyword], name: &str) -> bool {
+    keywords
+        .iter()
+        .any(|keyword| keyword.node.arg.as_deref() == Some(name))
+}
+
+fn is_strawberry_decorator(decorator: &Expr, names: &[&str]) -> bool {
+    let callee = callee_name(decorator);
+
+    callee.starts_with("strawberry.")
+        && names.contains(&callee.rsplit('.').next().unwrap_or(&callee))
+}
+
+fn field_from_keywords(
+    python_name: &str,
+    type_: Option<String>,
+    keywords: &[Keyword],
+    is_resolver: bool,
+) -> GraphQLField {
+    GraphQLField {
+        python_name: python_name.to_string(),
+        name: keyword_string(keywords, "name").unwrap_or_else(|| to_camel_case(python_name)),
+        type_,
+        description: keyword_string(keywords, "description"),
+        deprecation_reason: keyword_string(keywords, "deprecation_reason"),
+        is_resolver,
+    }
+}
+
+fn extract_resolver(
+    name: &str,
+    decorator_list: &[Expr],
+    returns: &Option<Box<Expr>>,
+) -> Option<GraphQLField> {
+    let decorator = decorator_list.iter().find(|decorator| {
+        is_strawberry_decorator(decorator, &["field", "mutation", "subscription"])
+    })?;
+
+    Some(field_from_keywords(
+        name,
+        returns.as_ref().map(|returns| returns.to_string()),
+        call_keywords(decorator),
+        true,
+    ))
+}
+
+/// Extracts the GraphQL view of a class decorated with `@strawberry.type`,
+/// `@strawberry.input` or `@strawberry.interface`.
+pub fn extract_graphql_type(
+    class_name: &str,
+    decorator_list: &[Expr],
+    body: &[Located<StmtKind>],
+) -> Option<GraphQLType> {
+    let (decorator, kind) = decorator_list.iter().find_map(|decorator| {
+        if is_strawberry_decorator(decorator, &["type"]) {
+            Some((decorator, GraphQLKind::Type))
+        } else if is_strawberry_decorator(decorator, &["input"]) {
+            Some((decorator, GraphQLKind::Input))
+        } else if is_strawberry_decorator(decorator, &["interface"]) {
+            Some((decorator, GraphQLKind::Interface))
+        } else {
+            None
+        }
+    })?;
+
+    let keywords = call_keywords(decorator);
+
+    let mut fields = Vec::new();
+
+    for statement in body {
+        match &statement.node {
+            StmtKind::AnnAssign {
+                target,
+                annotation,
+                value,
+                simple: _,
+            } => {
+                let name = match &target.node {
+                    ExprKind::Name { id, ctx: _ } => id,
+                    _ => continue,
+                };
+                let annotation = annotation.to_string();
+
+                // private fields are not exposed in the schema
+                if annotation.starts_with("strawberry.Private[")
+                    || annotation.starts_with("Private[")
+                {
+                    continue;
+                }
+
+                let field_keywords = match value {
+                    Some(value) if is_strawberry_decorator(value, &["field"]) => {
+                        call_keywords(value)
+                    }
+                    _ => &[],
+                };
+
+                fields.push(field_from_keywords(
+                    name,
+                    Some(annotation),
+                    field_keywords,
+                    has_keyword(field_keywords, "resolver"),
+                ));
+            }
+            StmtKind::FunctionDef {
+                name,
+                args: _,
+                body: _,
+                decorator_list,
+                returns,
+                type_comment: _,
+            }
+            | StmtKind::AsyncFunctionDef {
+                name,
+                args: _,
+                body: _,
+                decorator_list,
+                returns,
+                type_comment: _,
+            } => {
+                if let Some(field) = extract_resolver(name, decorator_list, returns) {
+                    fields.push(field);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(GraphQLType {
+        kind,
+        name: keyword_string(keywords, "name").unwrap_or_else(|| class_name.to_string()),
+        description: keyword_string(keywords, "description"),
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::docstrings::DocstringParsers;
+    use crate::strawberry::GraphQLKind;
+
+    #[test]
+    fn it_converts_names_to_camel_case() {
+        assert_eq!(super::to_camel_case("name"), "name");
+        assert_eq!(super::to_camel_case("is_input"), "isInput");
+        assert_eq!(super::to_camel_case("user_ID"), "userId");
+        assert_eq!(super::to_camel_case("a__b"), "a_B");
+    }
+
+    #[test]
+    fn it_extracts_graphql_types() {
+        let code = r#"
+        @strawberry.type(name="Person", description="A person")
+        class User:
+            """A user of the system"""
+
+            first_name: str
+            age: int = strawberry.field(description="The age")
+            nickname: Optional[str] = strawberry.field(
+                name="alias", deprecation_reason="Use first_name"
+            )
+            password: strawberry.Private[str]
+
+            @strawberry.field(description="The full name")
+            def full_name(self) -> str:
+                return self.first_name
+
+        @strawberry.input
+        class UserInput:
+            first_name: str
+
+        class NotAType:
+            field: str
+        "#;
+
+        let result = crate::extract::extract_with_parsers(
+            &textwrap::dedent(code),
+            "",
+            &DocstringParsers::default(),
+            None,
+        );
+
+        let user = result.classes[0].graphql.as_ref().unwrap();
+
+        assert_eq!(user.kind, GraphQLKind::Type);
+        assert_eq!(user.name, "Person");
+        assert_eq!(user.description, Some("A person".to_string()));
+        assert_eq!(user.fields.len(), 4);
+
+        assert_eq!(user.fields[0].python_name, "first_name");
+        assert_eq!(user.fields[0].name, "firstName");
+        assert_eq!(user.fields[0].type_, Some("str".to_string()));
+
+        assert_eq!(user.fields[1].name, "age");
+        assert_eq!(user.fields[1].description, Some("The age".to_string()));
+
+        assert_eq!(user.fields[2].python_name, "nickname");
+        assert_eq!(user.fields[2].name, "alias");
+        assert_eq!(
+            user.fields[2].deprecation_reason,
+            Some("Use first_name".to_string())
+        );
+
+        assert_eq!(user.fields[3].name, "fullName");
+        assert!(user.fields[3].is_resolver);
+        assert_eq!(
+            user.fields[3].description,
+            Some("The full name".to_string())
+        );
+
+        let user_input = result.classes[1].graphql.as_ref().unwrap();
+
+        assert_eq!(user_input.kind, GraphQLKind::Input);
+        assert_eq!(user_input.name, "UserInput");
+
+        assert!(result.classes[2].graphql.is_none());
+    }
+}